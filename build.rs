@@ -0,0 +1,88 @@
+//! Generates the solution registry of the main binary, which allows running all days in-process.
+use std::{env, fmt::Write, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    // solutions are named `<day>.rs`, or `<year>-<day>.rs` in workspaces that keep several years.
+    let mut days: Vec<(Option<u16>, u8)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let name = entry.file_name().into_string().ok()?;
+                    let stem = name.strip_suffix(".rs")?;
+                    let (year, day) = match stem.split_once('-') {
+                        Some((year, day)) if year.len() == 4 => (Some(year.parse().ok()?), day),
+                        Some(_) => return None,
+                        None => (None, stem),
+                    };
+                    if day.len() != 2 || year.is_some_and(|year| year < 2015) {
+                        return None;
+                    }
+                    let day = day.parse().ok().filter(|day| (1..=25).contains(day))?;
+                    Some((year, day))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    days.sort_unstable();
+
+    // NOTE: day modules are excluded from test builds to not run their tests twice, and from
+    // `dhat-heap` builds because every day declares its own global allocator.
+    let cfg = r#"#[cfg(not(any(test, feature = "dhat-heap")))]"#;
+
+    let mut registry = String::from("// @generated by build.rs\n\n");
+
+    let name = |year: &Option<u16>, day: &u8| match year {
+        Some(year) => format!("{year}-{day:02}"),
+        None => format!("{day:02}"),
+    };
+
+    for (year, day) in &days {
+        let name = name(year, day);
+        let path = bin_dir.join(format!("{name}.rs"));
+        // lints are reported when the day is built as its own binary.
+        writeln!(
+            registry,
+            "{cfg}\n#[allow(warnings)]\n#[path = {:?}]\nmod day_{};\n",
+            path.display().to_string(),
+            name.replace('-', "_")
+        )
+        .unwrap();
+    }
+
+    writeln!(
+        registry,
+        "{cfg}\npub static SOLUTIONS: &[advent_of_code::template::runner::Solution] = &["
+    )
+    .unwrap();
+
+    for (year, day) in &days {
+        let year_value = year.map_or_else(
+            || "None".into(),
+            |year| format!("Some(advent_of_code::year!({year}))"),
+        );
+        writeln!(
+            registry,
+            "    advent_of_code::template::runner::Solution {{ year: {year_value}, day: advent_of_code::day!({day}), run: day_{}::__run }},",
+            name(year, day).replace('-', "_")
+        )
+        .unwrap();
+    }
+
+    writeln!(registry, "];\n").unwrap();
+
+    writeln!(
+        registry,
+        "#[cfg(any(test, feature = \"dhat-heap\"))]\npub static SOLUTIONS: &[advent_of_code::template::runner::Solution] = &[];"
+    )
+    .unwrap();
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("registry.rs"), registry).unwrap();
+}
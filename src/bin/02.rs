@@ -1,13 +1,13 @@
-advent_of_code::solution!(2);
+advent_of_code::solution!(2, parse = parse);
 
 #[derive(Debug, Default)]
-struct Game {
+pub struct Game {
     id: u32,
     dice_sets: Vec<DiceSet>,
 }
 
 #[derive(Debug, Default)]
-struct DiceSet {
+pub struct DiceSet {
     blue: u32,
     green: u32,
     red: u32,
@@ -41,8 +41,7 @@ fn parse(input: &str) -> Vec<Game> {
         .collect()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let games = parse(input);
+pub fn part_one(games: &[Game]) -> Option<u32> {
     let sum = games
         .iter()
         .filter(|g| {
@@ -55,8 +54,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(sum)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let games = parse(input);
+pub fn part_two(games: &[Game]) -> Option<u32> {
     let sum = games
         .iter()
         .map(|g| {
@@ -90,13 +88,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&input);
         assert_eq!(result, Some(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&input);
         assert_eq!(result, Some(2286));
     }
 }
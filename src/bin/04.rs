@@ -1,9 +1,9 @@
 use std::collections::HashMap;
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(4, parse = parse);
 
 #[derive(Debug)]
-struct Card {
+pub struct Card {
     winners: Vec<u32>,
     candidates: Vec<u32>,
 }
@@ -37,8 +37,7 @@ fn parse(input: &str) -> HashMap<u32, Card> {
         .collect()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let cards = parse(input);
+pub fn part_one(cards: &HashMap<u32, Card>) -> Option<u32> {
     let sum = cards
         .values()
         .map(|c| {
@@ -57,8 +56,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(sum)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let cards = parse(input);
+pub fn part_two(cards: &HashMap<u32, Card>) -> Option<u32> {
     let mut cards_counts = cards
         .keys()
         .map(|id| (*id, 1_u32))
@@ -86,13 +84,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&input);
         assert_eq!(result, Some(13));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&input);
         assert_eq!(result, Some(30));
     }
 }
@@ -1,4 +1,6 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, answers, download, read, report, scaffold, solve, test, time, timings,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -6,74 +8,229 @@ use advent_of_code::template::Day;
 #[cfg(feature = "today")]
 use std::process;
 
+/// Solutions of all days present in `src/bin`, compiled into this binary to run them in-process.
+mod registry {
+    include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+}
+
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        commands::{
+            scaffold::ScaffoldOptions,
+            time::{ReadmeOptions, TimeOptions},
+            timings::ExportOptions,
+        },
+        runner::{BenchConfig, RunOptions},
+        Day, Year,
+    };
+    use std::{path::PathBuf, process, time::Duration};
 
     pub enum AppArguments {
         Download {
+            year: Option<Year>,
             day: Day,
         },
         Read {
+            year: Option<Year>,
             day: Day,
         },
+        Report {
+            year: Option<Year>,
+            profile: Option<String>,
+        },
         Scaffold {
+            year: Option<Year>,
             day: Day,
-            download: bool,
+            options: ScaffoldOptions,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
-            submit: Option<u8>,
+            watch: bool,
+            options: RunOptions,
+            inputs_dir: Option<PathBuf>,
+        },
+        Test {
+            year: Option<Year>,
+            day: Option<Day>,
+            release: bool,
         },
         All {
+            year: Option<Year>,
             release: bool,
+            jobs: usize,
+            verify: bool,
+            timeout: Option<Duration>,
+        },
+        AnswersSet {
+            year: Option<Year>,
+            day: Day,
+            part: u8,
+            answer: String,
         },
         Time {
-            all: bool,
             day: Option<Day>,
-            store: bool,
+            options: TimeOptions,
+        },
+        TimingsExport {
+            year: Option<Year>,
+            profile: Option<String>,
+            options: ExportOptions,
+        },
+        TimingsDiff {
+            year: Option<Year>,
+            a: String,
+            b: String,
+            readme: Option<ReadmeOptions>,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            year: Option<Year>,
+        },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+        // every command takes a year, for workspaces that keep several years.
+        let year: Option<Year> = args.opt_value_from_str("--year")?;
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
+                year,
                 release: args.contains("--release"),
+                jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                verify: args.contains("--verify"),
+                timeout: parse_timeout(&mut args)?,
+            },
+            Some("answers") => match args.subcommand()?.as_deref() {
+                Some("set") => AppArguments::AnswersSet {
+                    year,
+                    day: args.free_from_str()?,
+                    part: args.free_from_str()?,
+                    answer: args.free_from_str()?,
+                },
+                _ => {
+                    eprintln!("Unknown answers command. Usage: answers set <day> <part> <answer>");
+                    process::exit(1);
+                }
             },
             Some("time") => {
-                let all = args.contains("--all");
-                let store = args.contains("--store");
+                let options = TimeOptions {
+                    run_all: args.contains("--all"),
+                    store: args.contains("--store"),
+                    bench: parse_bench_config(&mut args)?,
+                    timeout: parse_timeout(&mut args)?,
+                    // NOTE: benchmarks run serially by default to not disturb timings.
+                    jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                    regression_threshold: args
+                        .opt_value_from_str("--regression-threshold")?
+                        .unwrap_or(10_f64),
+                    fail_on_regression: args.contains("--fail-on-regression"),
+                    profile: args.opt_value_from_str("--profile")?,
+                    compare: args.opt_value_from_str("--compare")?,
+                    readme: ReadmeOptions {
+                        year,
+                        ..parse_readme_options(&mut args)?
+                    },
+                    year,
+                };
 
                 AppArguments::Time {
-                    all,
                     day: args.opt_free_from_str()?,
-                    store,
+                    options,
                 }
             }
+            Some("timings") => match args.subcommand()?.as_deref() {
+                Some("export") => AppArguments::TimingsExport {
+                    year,
+                    profile: args.opt_value_from_str("--profile")?,
+                    options: ExportOptions {
+                        format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                        sort: args.opt_value_from_str("--sort")?.unwrap_or_default(),
+                        from: args.opt_value_from_str("--from")?,
+                        to: args.opt_value_from_str("--to")?,
+                    },
+                },
+                Some("diff") => {
+                    let update_readme = args.contains("--readme");
+                    let readme = ReadmeOptions {
+                        year,
+                        ..parse_readme_options(&mut args)?
+                    };
+                    AppArguments::TimingsDiff {
+                        year,
+                        readme: update_readme.then_some(readme),
+                        a: args.free_from_str()?,
+                        b: args.free_from_str()?,
+                    }
+                }
+                _ => {
+                    eprintln!("Unknown timings command. Usage: timings export [--profile <name>] [--format csv|md|json] [--sort day|total] [--from <day>] [--to <day>] or timings diff <a> <b> [--readme]");
+                    process::exit(1);
+                }
+            },
             Some("download") => AppArguments::Download {
+                year,
                 day: args.free_from_str()?,
             },
             Some("read") => AppArguments::Read {
+                year,
                 day: args.free_from_str()?,
             },
+            Some("report") => AppArguments::Report {
+                year,
+                profile: args.opt_value_from_str("--profile")?,
+            },
             Some("scaffold") => AppArguments::Scaffold {
+                year,
                 day: args.free_from_str()?,
-                download: args.contains("--download"),
-            },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+                options: ScaffoldOptions {
+                    download: args.contains("--download"),
+                    template: args.opt_value_from_str("--template")?,
+                    return_type: args
+                        .opt_value_from_str("--return-type")?
+                        .unwrap_or_else(|| ScaffoldOptions::default().return_type),
+                },
             },
+            Some("solve") => {
+                let watch = args.contains("--watch");
+                let inputs_dir = args.opt_value_from_str("--inputs-dir")?;
+                if watch && inputs_dir.is_some() {
+                    eprintln!("`--inputs-dir` can not be combined with `--watch`.");
+                    process::exit(1);
+                }
+                AppArguments::Solve {
+                    day: args.free_from_str()?,
+                    release: args.contains("--release"),
+                    dhat: args.contains("--dhat"),
+                    watch,
+                    options: RunOptions {
+                        submit: args.opt_value_from_str("--submit")?,
+                        time: args.contains("--time"),
+                        bench: parse_bench_config(&mut args)?,
+                        timeout: parse_timeout(&mut args)?,
+                        input: args.opt_value_from_str("--input")?.unwrap_or_default(),
+                        year,
+                        ..RunOptions::default()
+                    },
+                    inputs_dir,
+                }
+            }
+            Some("test") => {
+                let release = args.contains("--release");
+                let all = args.contains("--all");
+                let day = args.opt_free_from_str()?;
+                if day.is_none() && !all {
+                    eprintln!("Specify a day or --all. Usage: test <day> or test --all");
+                    process::exit(1);
+                }
+                AppArguments::Test { year, day, release }
+            }
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today { year },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -91,6 +248,56 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Benchmark settings default to the environment and can be overridden per run.
+    fn parse_bench_config(
+        args: &mut pico_args::Arguments,
+    ) -> Result<BenchConfig, pico_args::Error> {
+        let mut config = BenchConfig::from_env();
+
+        if let Some(ms) = args.opt_value_from_str("--warmup")? {
+            config.warmup = Duration::from_millis(ms);
+        }
+        if let Some(ms) = args.opt_value_from_str("--budget")? {
+            config.budget = Duration::from_millis(ms);
+        }
+        if let Some(n) = args.opt_value_from_str("--min-samples")? {
+            config.min_samples = n;
+        }
+        if let Some(n) = args.opt_value_from_str("--max-samples")? {
+            config.max_samples = n;
+        }
+
+        Ok(config)
+    }
+
+    /// The benchmark table is written to the `README.md` by default.
+    fn parse_readme_options(
+        args: &mut pico_args::Arguments,
+    ) -> Result<ReadmeOptions, pico_args::Error> {
+        let mut options = ReadmeOptions::default();
+
+        if let Some(file) = args.opt_value_from_str("--readme-file")? {
+            options.file = file;
+        }
+        if let Some(heading) = args.opt_value_from_str("--readme-heading")? {
+            options.heading = heading;
+        }
+        options.table = args.opt_value_from_str("--readme-table")?;
+        options.chart = args.contains("--chart");
+
+        Ok(options)
+    }
+
+    /// The step timeout defaults to the environment and can be overridden per run.
+    fn parse_timeout(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Duration>, pico_args::Error> {
+        Ok(args
+            .opt_value_from_str("--timeout")?
+            .map(Duration::from_millis)
+            .or_else(RunOptions::timeout_from_env))
+    }
 }
 
 fn main() {
@@ -100,29 +307,70 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download } => {
-                scaffold::handle(day);
-                if download {
-                    download::handle(day);
-                }
+            AppArguments::All {
+                year,
+                release,
+                jobs,
+                verify,
+                timeout,
+            } => all::handle(year, release, jobs, verify, timeout, registry::SOLUTIONS),
+            AppArguments::AnswersSet {
+                year,
+                day,
+                part,
+                answer,
+            } => {
+                answers::handle_set(year, day, part, &answer);
+            }
+            AppArguments::Time { day, options } => {
+                time::handle(day, &options, registry::SOLUTIONS);
+            }
+            AppArguments::TimingsExport {
+                year,
+                profile,
+                options,
+            } => {
+                timings::handle_export(year, profile.as_deref(), &options);
+            }
+            AppArguments::TimingsDiff { year, a, b, readme } => {
+                timings::handle_diff(year, &a, &b, readme.as_ref());
+            }
+            AppArguments::Download { year, day } => download::handle(year, day),
+            AppArguments::Read { year, day } => read::handle(year, day),
+            AppArguments::Report { year, profile } => report::handle(year, profile.as_deref()),
+            AppArguments::Scaffold { year, day, options } => {
+                scaffold::handle(year, day, &options);
             }
             AppArguments::Solve {
                 day,
                 release,
                 dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
+                watch,
+                options,
+                inputs_dir,
+            } => match inputs_dir {
+                Some(dir) => solve::handle_inputs_dir(
+                    day,
+                    &dir,
+                    release,
+                    dhat,
+                    &options,
+                    registry::SOLUTIONS,
+                ),
+                None if watch => solve::handle_watch(day, release, dhat, &options),
+                None => solve::handle(day, release, dhat, &options),
+            },
+            AppArguments::Test { year, day, release } => test::handle(year, day, release),
             #[cfg(feature = "today")]
-            AppArguments::Today => {
+            AppArguments::Today { year } => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day);
-                        download::handle(day);
-                        read::handle(day)
+                        let options = scaffold::ScaffoldOptions {
+                            download: true,
+                            ..Default::default()
+                        };
+                        scaffold::handle(year, day, &options);
+                        read::handle(year, day)
                     }
                     None => {
                         eprintln!(
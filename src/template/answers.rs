@@ -0,0 +1,320 @@
+use std::{collections::HashMap, fs, io, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    data_dir,
+    runner::{Record, Step},
+    Day, Year,
+};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// Represents the known, correct answers for a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Represents the known answers for the real puzzle inputs.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+/// A part whose answer does not match the known answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    pub day: Day,
+    pub part: u8,
+    pub expected: String,
+    pub actual: Option<String>,
+}
+
+impl Answers {
+    /// Dehydrate answers to the JSON file of a year, `data/answers.json` if none is passed.
+    pub fn store_file(&self, year: Option<Year>) -> Result<(), io::Error> {
+        let path = data_dir(year).join(ANSWERS_FILE_NAME);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from the JSON file of a year. If not present, returns empty answers.
+    pub fn read_from_file(year: Option<Year>) -> Self {
+        let s = match fs::read_to_string(data_dir(year).join(ANSWERS_FILE_NAME)) {
+            Ok(s) => Answers::try_from(s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e.to_string()),
+        };
+
+        match s {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("{e}");
+                Answers::default()
+            }
+        }
+    }
+
+    /// Returns the known answer for a part, if any.
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        let answer = self.data.iter().find(|a| a.day == day)?;
+        match part {
+            1 => answer.part_1.as_deref(),
+            2 => answer.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Records the known answer for a part, replacing a previous answer.
+    /// Parts the day does not have are ignored.
+    pub fn set(&mut self, day: Day, part: u8, value: &str) {
+        if !(1..=day.parts()).contains(&part) {
+            return;
+        }
+
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        let answer = &mut self.data[index];
+        match part {
+            1 => answer.part_1 = Some(value.into()),
+            2 => answer.part_2 = Some(value.into()),
+            _ => {}
+        }
+    }
+
+    /// Compares the records of a day against the known answers. Parts without a known answer are skipped,
+    /// parts with a known answer but no record are reported as a mismatch.
+    pub fn verify(&self, day: Day, records: &[Record]) -> Vec<Mismatch> {
+        (1..=day.parts())
+            .filter_map(|part| {
+                let expected = self.get(day, part)?;
+                let actual = records
+                    .iter()
+                    .find(|r| r.step == Step::Part(part))
+                    .and_then(|r| r.answer.clone());
+
+                if actual.as_deref() == Some(expected) {
+                    return None;
+                }
+
+                Some(Mismatch {
+                    day,
+                    part,
+                    expected: expected.into(),
+                    actual,
+                })
+            })
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "part_1".into(),
+            value
+                .part_1
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "part_2".into(),
+            value
+                .part_2
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+            _ => Err(format!("Expected answer.{key} to be null or string.")),
+        };
+
+        Ok(Answer {
+            day,
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        day,
+        template::runner::{Record, Status, Step},
+    };
+
+    use super::{Answer, Answers, Mismatch};
+
+    fn get_mock_answers() -> Answers {
+        Answers {
+            data: vec![Answer {
+                day: day!(1),
+                part_1: Some("142".into()),
+                part_2: None,
+            }],
+        }
+    }
+
+    fn record(part: u8, answer: Option<&str>) -> Record {
+        Record {
+            step: Step::Part(part),
+            status: Status::Completed,
+            answer: answer.map(Into::into),
+            duration: Duration::ZERO,
+            samples: 1,
+            stats: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let answers = get_mock_answers();
+        let json = tinyjson::JsonValue::from(answers.clone())
+            .stringify()
+            .unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+
+    #[test]
+    fn sets_answers() {
+        let mut answers = get_mock_answers();
+        answers.set(day!(3), 2, "42");
+        answers.set(day!(1), 2, "281");
+        answers.set(day!(1), 1, "143");
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.get(day!(1), 1), Some("143"));
+        assert_eq!(answers.get(day!(1), 2), Some("281"));
+        assert_eq!(answers.get(day!(3), 1), None);
+        assert_eq!(answers.get(day!(3), 2), Some("42"));
+
+        answers.set(day!(25), 2, "1");
+        answers.set(day!(4), 3, "1");
+        assert_eq!(answers.data.len(), 2);
+    }
+
+    #[test]
+    fn verifies_matching_answers() {
+        let answers = get_mock_answers();
+        let mismatches = answers.verify(day!(1), &[record(1, Some("142")), record(2, Some("1"))]);
+        assert_eq!(mismatches, vec![]);
+    }
+
+    #[test]
+    fn verifies_mismatching_answers() {
+        let answers = get_mock_answers();
+        let mismatches = answers.verify(day!(1), &[record(1, None)]);
+        assert_eq!(
+            mismatches,
+            vec![Mismatch {
+                day: day!(1),
+                part: 1,
+                expected: "142".into(),
+                actual: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn verifies_parts_without_records() {
+        let mut answers = get_mock_answers();
+        answers.set(day!(1), 2, "281");
+        let mismatches = answers.verify(day!(1), &[record(1, Some("142"))]);
+        assert_eq!(
+            mismatches,
+            vec![Mismatch {
+                day: day!(1),
+                part: 2,
+                expected: "281".into(),
+                actual: None,
+            }]
+        );
+        assert_eq!(answers.verify(day!(1), &[]).len(), 2);
+    }
+}
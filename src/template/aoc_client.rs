@@ -0,0 +1,630 @@
+/// Minimal client for the Advent of Code website: downloads inputs and puzzle descriptions and submits answers.
+/// Authenticates with the `session` cookie of the website, read from `AOC_SESSION` or an `.adventofcode.session` file.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::template::{data_dir, Day, Year};
+
+pub const SESSION_ENV: &str = "AOC_SESSION";
+/// Overrides the address of the website, e.g. to test against a local server.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const SESSION_FILE_NAME: &str = ".adventofcode.session";
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+#[derive(Debug)]
+pub enum Error {
+    /// No session token in the environment or a session file.
+    MissingSession,
+    /// `AOC_YEAR` is not set to a valid year.
+    MissingYear,
+    /// The website rejected the session token, it most likely expired.
+    BadSession,
+    /// The puzzle does not exist or is not unlocked yet.
+    PuzzleNotFound(Day),
+    /// Too many requests, or an answer was submitted too recently. Holds the wait time if the website reported one.
+    RateLimited(Option<Duration>),
+    /// The website responded with an unexpected status code.
+    Http(u16),
+    /// The website could not be reached.
+    Transport(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingSession => write!(
+                f,
+                "no session token found. Set `{SESSION_ENV}` or put it in ~/{SESSION_FILE_NAME}."
+            ),
+            Error::MissingYear => write!(f, "`AOC_YEAR` is not set to a valid year."),
+            Error::BadSession => write!(
+                f,
+                "the session token was rejected, it might have expired. Refresh it from your browser."
+            ),
+            Error::PuzzleNotFound(day) => {
+                write!(f, "the puzzle of day {day} does not exist or is not unlocked yet.")
+            }
+            Error::RateLimited(Some(wait)) => write!(f, "rate limited, {wait:?} left to wait."),
+            Error::RateLimited(None) => write!(f, "rate limited, try again later."),
+            Error::Http(status) => write!(f, "unexpected response with status {status}."),
+            Error::Transport(e) => write!(f, "could not reach the website: {e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Whether a wrong answer was too high or too low, if the website told.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+impl Display for Hint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Hint::TooHigh => write!(f, "your answer is too high"),
+            Hint::TooLow => write!(f, "your answer is too low"),
+        }
+    }
+}
+
+/// The verdict of the website on a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    Correct,
+    /// Holds the hint of the website and how long to wait before the next answer may be submitted.
+    Incorrect {
+        hint: Option<Hint>,
+        wait: Option<Duration>,
+    },
+    /// The part was already solved, or part 1 is not solved yet.
+    WrongLevel,
+}
+
+impl Display for SubmitOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitOutcome::Correct => write!(f, "That's the right answer! ⭐"),
+            SubmitOutcome::Incorrect {
+                hint: Some(hint), ..
+            } => {
+                write!(f, "That's not the right answer, {hint}.")
+            }
+            SubmitOutcome::Incorrect { hint: None, .. } => {
+                write!(f, "That's not the right answer.")
+            }
+            SubmitOutcome::WrongLevel => write!(
+                f,
+                "You don't seem to be solving the right level. Did you already complete it?"
+            ),
+        }
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    year: Year,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    #[must_use]
+    pub fn new(base_url: &str, session: &str, year: Year) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.trim().into(),
+            year,
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    /// Creates a client for `year`, or `AOC_YEAR` if none is passed, with the session token of the user.
+    pub fn from_env(year: Option<Year>) -> Result<Self, Error> {
+        let session = read_session().ok_or(Error::MissingSession)?;
+        let year = year.or_else(get_year).ok_or(Error::MissingYear)?;
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(Client::new(&base_url, &session, year))
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /// Fetches the puzzle input of the user.
+    pub fn input(&self, day: Day) -> Result<String, Error> {
+        let request = self
+            .agent
+            .get(&format!("{}/input", self.day_url(day)))
+            .set("Cookie", &self.cookie());
+        read_body(request.call(), day)
+    }
+
+    /// Fetches the puzzle description as markdown, including part 2 once it is unlocked.
+    pub fn puzzle(&self, day: Day) -> Result<String, Error> {
+        let request = self
+            .agent
+            .get(&self.day_url(day))
+            .set("Cookie", &self.cookie());
+        let html = read_body(request.call(), day)?;
+        Ok(html_to_markdown(&html, &self.base_url))
+    }
+
+    /// Submits the answer of a part.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<SubmitOutcome, Error> {
+        let request = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(day)))
+            .set("Cookie", &self.cookie());
+        let html = read_body(
+            request.send_form(&[("level", &part.to_string()), ("answer", answer)]),
+            day,
+        )?;
+        parse_submit_response(&html)
+    }
+}
+
+#[must_use]
+pub fn get_input_path(year: Option<Year>, day: Day) -> String {
+    data_dir(year)
+        .join("inputs")
+        .join(format!("{day}.txt"))
+        .display()
+        .to_string()
+}
+
+#[must_use]
+pub fn get_puzzle_path(year: Option<Year>, day: Day) -> String {
+    data_dir(year)
+        .join("puzzles")
+        .join(format!("{day}.md"))
+        .display()
+        .to_string()
+}
+
+pub(crate) fn get_year() -> Option<Year> {
+    env::var("AOC_YEAR").ok()?.parse().ok()
+}
+
+/// Reads the session token from `AOC_SESSION`, `~/.adventofcode.session` or `<config dir>/adventofcode.session`.
+fn read_session() -> Option<String> {
+    if let Ok(session) = env::var(SESSION_ENV) {
+        return Some(session).filter(|x| !x.trim().is_empty());
+    }
+
+    let home = env::var_os("HOME").map(PathBuf::from);
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|x| x.join(".config")));
+
+    [
+        home.map(|x| x.join(SESSION_FILE_NAME)),
+        config_dir.map(|x| x.join(SESSION_FILE_NAME.trim_start_matches('.'))),
+    ]
+    .into_iter()
+    .flatten()
+    .find_map(|path| fs::read_to_string(path).ok())
+    .map(|x| x.trim().to_string())
+    .filter(|x| !x.is_empty())
+}
+
+fn read_body(response: Result<ureq::Response, ureq::Error>, day: Day) -> Result<String, Error> {
+    match response {
+        Ok(response) => Ok(response.into_string()?),
+        // NOTE: the website answers requests with an invalid session with 400 or 500.
+        Err(ureq::Error::Status(400 | 401 | 403 | 500, _)) => Err(Error::BadSession),
+        Err(ureq::Error::Status(404, _)) => Err(Error::PuzzleNotFound(day)),
+        Err(ureq::Error::Status(429, _)) => Err(Error::RateLimited(None)),
+        Err(ureq::Error::Status(status, _)) => Err(Error::Http(status)),
+        Err(ureq::Error::Transport(e)) => Err(Error::Transport(e.to_string())),
+    }
+}
+
+fn parse_submit_response(html: &str) -> Result<SubmitOutcome, Error> {
+    let text = html_to_text(html);
+
+    if text.contains("That's the right answer") {
+        Ok(SubmitOutcome::Correct)
+    } else if text.contains("That's not the right answer") {
+        let hint = [Hint::TooHigh, Hint::TooLow]
+            .into_iter()
+            .find(|x| text.contains(&x.to_string()));
+        Ok(SubmitOutcome::Incorrect {
+            hint,
+            wait: parse_wait(&text),
+        })
+    } else if text.contains("You gave an answer too recently") {
+        Err(Error::RateLimited(parse_wait(&text)))
+    } else if text.contains("You don't seem to be solving the right level") {
+        Ok(SubmitOutcome::WrongLevel)
+    } else if text.contains("log in") {
+        Err(Error::BadSession)
+    } else {
+        Err(Error::Http(200))
+    }
+}
+
+/// Parses the wait time of a response, e.g. "You have 1m 36s left to wait." or "Please wait 5 minutes before trying again."
+fn parse_wait(text: &str) -> Option<Duration> {
+    if let Some((_, rest)) = text.split_once("You have ") {
+        let (wait, _) = rest.split_once(" left to wait")?;
+        let secs = wait.split_whitespace().try_fold(0, |acc, x| {
+            let (value, factor) = match x.strip_suffix('m') {
+                Some(minutes) => (minutes, 60),
+                None => (x.strip_suffix('s')?, 1),
+            };
+            Some(acc + value.parse::<u64>().ok()? * factor)
+        })?;
+        return Some(Duration::from_secs(secs));
+    }
+
+    let (_, rest) = text.split_once("wait ")?;
+    let (amount, _) = rest.split_once(" minute")?;
+    let minutes = match amount {
+        "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "ten" => 10,
+        x => x.parse().ok()?,
+    };
+    Some(Duration::from_secs(minutes * 60))
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Splits html into text and tags, e.g. `<p>a</p>` into `[Tag("p"), Text("a"), Tag("/p")]`.
+fn tokenize(html: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        if start > 0 {
+            tokens.push(Token::Text(decode_entities(&rest[..start])));
+        }
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        tokens.push(Token::Tag(rest[start + 1..start + end].to_string()));
+        rest = &rest[start + end + 1..];
+    }
+
+    if !rest.is_empty() {
+        tokens.push(Token::Text(decode_entities(rest)));
+    }
+
+    tokens
+}
+
+enum Token {
+    Tag(String),
+    Text(String),
+}
+
+fn tag_name(tag: &str) -> &str {
+    tag.split(|c: char| c.is_whitespace() || c == '/')
+        .find(|x| !x.is_empty())
+        .unwrap_or_default()
+}
+
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = tag[start..].find('"')?;
+    Some(&tag[start..start + end])
+}
+
+/// Strips all tags from html.
+fn html_to_text(html: &str) -> String {
+    tokenize(html)
+        .into_iter()
+        .filter_map(|x| match x {
+            Token::Text(text) => Some(text),
+            Token::Tag(_) => None,
+        })
+        .collect()
+}
+
+/// Converts the `<article>` elements of a puzzle page to markdown.
+fn html_to_markdown(html: &str, base_url: &str) -> String {
+    let mut out = String::new();
+    let mut in_article = false;
+    let mut in_pre = false;
+    let mut link: Option<String> = None;
+
+    for token in tokenize(html) {
+        match token {
+            Token::Text(text) if in_article => out.push_str(&text),
+            Token::Text(_) => {}
+            Token::Tag(tag) => {
+                let closing = tag.starts_with('/');
+                match (tag_name(&tag), closing) {
+                    ("article", false) => in_article = true,
+                    ("article", true) => {
+                        in_article = false;
+                        out.push_str("\n\n");
+                    }
+                    _ if !in_article => {}
+                    ("h2", false) => out.push_str("## "),
+                    ("h2" | "p" | "ul", true) => out.push_str("\n\n"),
+                    ("pre", false) => {
+                        in_pre = true;
+                        out.push_str("```\n");
+                    }
+                    ("pre", true) => {
+                        in_pre = false;
+                        if !out.ends_with('\n') {
+                            out.push('\n');
+                        }
+                        out.push_str("```\n\n");
+                    }
+                    ("code", _) if !in_pre => out.push('`'),
+                    ("em", _) if !in_pre => out.push('*'),
+                    ("li", false) => out.push_str("- "),
+                    ("li", true) => out.push('\n'),
+                    ("a", false) => {
+                        link = attribute(&tag, "href").map(|href| {
+                            if href.starts_with('/') {
+                                format!("{base_url}{href}")
+                            } else {
+                                href.to_string()
+                            }
+                        });
+                        if link.is_some() {
+                            out.push('[');
+                        }
+                    }
+                    ("a", true) => {
+                        if let Some(href) = link.take() {
+                            out.push_str(&format!("]({href})"));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    // collapse the blank lines left behind by nested block elements.
+    let mut markdown = out.trim().to_string();
+    while markdown.contains("\n\n\n") {
+        markdown = markdown.replace("\n\n\n", "\n\n");
+    }
+    markdown.push('\n');
+    markdown
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Downloads the input and puzzle description of a day to `data/`, or `data/<year>` if a year is passed.
+pub fn download(year: Option<Year>, day: Day) -> Result<(), Error> {
+    let client = Client::from_env(year)?;
+    let input_path = get_input_path(year, day);
+    let puzzle_path = get_puzzle_path(year, day);
+
+    write_file(&input_path, &client.input(day)?)?;
+    write_file(&puzzle_path, &client.puzzle(day)?)?;
+
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+/// Fetches the puzzle description of a day, stores it in `data/puzzles` and prints it.
+pub fn read(year: Option<Year>, day: Day) -> Result<(), Error> {
+    let client = Client::from_env(year)?;
+    let puzzle = client.puzzle(day)?;
+    write_file(&get_puzzle_path(year, day), &puzzle)?;
+    println!("{puzzle}");
+    Ok(())
+}
+
+fn write_file(path: &str, contents: &str) -> Result<(), io::Error> {
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    use std::time::Duration;
+
+    use super::{html_to_markdown, Client, Error, Hint, SubmitOutcome};
+    use crate::{day, year};
+
+    /// Serves a single request with `status` and `body`, returning the raw request.
+    fn serve(status: u16, body: &'static str) -> (Client, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(x) = line.to_lowercase().strip_prefix("content-length: ") {
+                    content_length = x.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+            request.push_str(&String::from_utf8(request_body).unwrap());
+
+            let response = format!(
+                "HTTP/1.1 {status} Status\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+
+        (Client::new(&base_url, "abc123\n", year!(2023)), handle)
+    }
+
+    #[test]
+    fn fetches_inputs() {
+        let (client, server) = serve(200, "1abc2\npqr3stu8vwx\n");
+        assert_eq!(client.input(day!(1)).unwrap(), "1abc2\npqr3stu8vwx\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2023/day/1/input HTTP/1.1"));
+        assert!(request.contains("Cookie: session=abc123\r\n"));
+    }
+
+    #[test]
+    fn maps_status_codes_to_errors() {
+        let (client, _) = serve(
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+        );
+        assert!(matches!(client.input(day!(1)), Err(Error::BadSession)));
+
+        let (client, _) = serve(
+            404,
+            "Please don't repeatedly request this endpoint before it unlocks!",
+        );
+        assert!(
+            matches!(client.input(day!(25)), Err(Error::PuzzleNotFound(day)) if day == day!(25))
+        );
+
+        let (client, _) = serve(429, "");
+        assert!(matches!(
+            client.puzzle(day!(1)),
+            Err(Error::RateLimited(None))
+        ));
+
+        let (client, _) = serve(502, "");
+        assert!(matches!(client.puzzle(day!(1)), Err(Error::Http(502))));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (client, server) = serve(
+            200,
+            "<main><article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.</p></article></main>",
+        );
+        assert_eq!(
+            client.submit(day!(3), 2, "4361").unwrap(),
+            SubmitOutcome::Correct
+        );
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2023/day/3/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=4361"));
+    }
+
+    #[test]
+    fn parses_submit_responses() {
+        let (client, _) = serve(
+            200,
+            "<article><p>That's not the right answer; your answer is too high.  If you're stuck, ... Please wait one minute before trying again.</p></article>",
+        );
+        assert_eq!(
+            client.submit(day!(1), 1, "1").unwrap(),
+            SubmitOutcome::Incorrect {
+                hint: Some(Hint::TooHigh),
+                wait: Some(Duration::from_secs(60))
+            }
+        );
+
+        let (client, _) = serve(
+            200,
+            "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 36s left to wait. <a href=\"/2023/day/1\">[Return to Day 1]</a></p></article>",
+        );
+        assert!(
+            matches!(client.submit(day!(1), 1, "1"), Err(Error::RateLimited(Some(wait))) if wait == Duration::from_secs(96))
+        );
+
+        let (client, _) = serve(
+            200,
+            "<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>",
+        );
+        assert_eq!(
+            client.submit(day!(1), 1, "1").unwrap(),
+            SubmitOutcome::WrongLevel
+        );
+    }
+
+    #[test]
+    fn converts_puzzles_to_markdown() {
+        let html = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2><p>Something is <em>wrong</em> with <a href="/2023/about">global snow production</a>.</p>
+<p>For example:</p>
+<pre><code>1abc2
+a1b2c3d4e5f
+</code></pre>
+<ul>
+<li>In <code>1abc2</code>, the values are <code>12</code> &amp; <code>&lt;3</code>.</li>
+</ul>
+</article>
+<p>Your puzzle answer was <code>142</code>.</p>
+</main></body></html>"#;
+
+        assert_eq!(
+            html_to_markdown(html, "https://adventofcode.com"),
+            [
+                "## --- Day 1: Trebuchet?! ---",
+                "",
+                "Something is *wrong* with [global snow production](https://adventofcode.com/2023/about).",
+                "",
+                "For example:",
+                "",
+                "```",
+                "1abc2",
+                "a1b2c3d4e5f",
+                "```",
+                "",
+                "- In `1abc2`, the values are `12` & `<3`.",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+}
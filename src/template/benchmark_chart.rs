@@ -0,0 +1,158 @@
+/// Module that renders the readme benchmarks as a self-contained SVG bar chart.
+use std::{fmt::Write, time::Duration};
+
+use crate::template::timings::{StepTiming, Timings};
+
+const MARGIN_LEFT: f64 = 64.0;
+const MARGIN_RIGHT: f64 = 16.0;
+const MARGIN_TOP: f64 = 40.0;
+const MARGIN_BOTTOM: f64 = 32.0;
+const PLOT_HEIGHT: f64 = 240.0;
+const GROUP_WIDTH: f64 = 32.0;
+const BAR_WIDTH: f64 = 12.0;
+
+const COLORS: [&str; 2] = ["#4e79a7", "#f28e2b"];
+
+/// Renders part 1 and part 2 of every day as a pair of bars on a logarithmic time axis.
+#[allow(clippy::cast_precision_loss)]
+pub fn render(timings: &Timings) -> String {
+    let nanos = |x: &Option<StepTiming>| x.map(|x| (x.duration.as_nanos() as f64).max(1_f64));
+
+    let values: Vec<f64> = timings
+        .data
+        .iter()
+        .flat_map(|t| [nanos(&t.part_1), nanos(&t.part_2)])
+        .flatten()
+        .collect();
+
+    // the axis spans whole powers of ten around all values.
+    let lo = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let hi = values.iter().copied().fold(0_f64, f64::max);
+    let (lo, hi) = if values.is_empty() {
+        (0_f64, 1_f64)
+    } else {
+        let (lo, hi) = (lo.log10().floor(), hi.log10().ceil());
+        (lo, if hi > lo { hi } else { lo + 1_f64 })
+    };
+
+    let width = MARGIN_LEFT + MARGIN_RIGHT + GROUP_WIDTH * (timings.data.len().max(1) as f64);
+    let height = MARGIN_TOP + PLOT_HEIGHT + MARGIN_BOTTOM;
+    let baseline = MARGIN_TOP + PLOT_HEIGHT;
+    let y = |nanos: f64| baseline - PLOT_HEIGHT * (nanos.log10() - lo) / (hi - lo);
+
+    let mut svg = String::new();
+
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="sans-serif" font-size="11">"#
+    );
+    let _ = writeln!(
+        svg,
+        r##"<rect width="{width}" height="{height}" fill="#ffffff"/>"##
+    );
+
+    for (i, (label, color)) in ["Part 1", "Part 2"].iter().zip(COLORS).enumerate() {
+        let x = MARGIN_LEFT + 64.0 * i as f64;
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{x}" y="12" width="10" height="10" fill="{color}"/><text x="{}" y="21">{label}</text>"#,
+            x + 14.0
+        );
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    for exponent in (lo as u32)..=(hi as u32) {
+        let tick = y(10_f64.powi(exponent as i32));
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{MARGIN_LEFT}" y1="{tick:.1}" x2="{}" y2="{tick:.1}" stroke="#dddddd"/><text x="{}" y="{:.1}" text-anchor="end">{:?}</text>"##,
+            width - MARGIN_RIGHT,
+            MARGIN_LEFT - 6.0,
+            tick + 4.0,
+            Duration::from_nanos(10_u64.pow(exponent))
+        );
+    }
+
+    for (i, timing) in timings.data.iter().enumerate() {
+        let group = MARGIN_LEFT + GROUP_WIDTH * i as f64;
+
+        for (j, (part, color)) in [&timing.part_1, &timing.part_2]
+            .into_iter()
+            .zip(COLORS)
+            .enumerate()
+        {
+            let Some(value) = nanos(part) else {
+                continue;
+            };
+
+            let x = group + (GROUP_WIDTH - 2.0 * BAR_WIDTH) / 2.0 + BAR_WIDTH * j as f64;
+            let top = y(value);
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{x}" y="{top:.1}" width="{BAR_WIDTH}" height="{:.1}" fill="{color}"><title>Day {} part {}: {:.1?}</title></rect>"#,
+                baseline - top,
+                timing.day.into_inner(),
+                j + 1,
+                part.map(|x| x.duration).unwrap_or_default()
+            );
+        }
+
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="middle">{}</text>"#,
+            group + GROUP_WIDTH / 2.0,
+            baseline + 16.0,
+            timing.day.into_inner()
+        );
+    }
+
+    let _ = writeln!(
+        svg,
+        r##"<line x1="{MARGIN_LEFT}" y1="{baseline}" x2="{}" y2="{baseline}" stroke="#333333"/>"##,
+        width - MARGIN_RIGHT
+    );
+    svg.push_str("</svg>\n");
+
+    svg
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::render;
+    use crate::{
+        day,
+        template::timings::{
+            test_helpers::{step, timing},
+            Timings,
+        },
+    };
+
+    #[test]
+    fn renders_bars_per_part() {
+        let timings = Timings {
+            data: vec![
+                timing(day!(1), step(5), step(1), step(50_000)),
+                timing(day!(2), None, step(2), None),
+            ],
+            history: vec![],
+        };
+
+        let svg = render(&timings);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<title>").count(), 3);
+        assert!(svg.contains("<title>Day 1 part 2: 50.0ms</title>"));
+        // ticks from 1µs to 100ms.
+        assert!(svg.contains(">1µs</text>"));
+        assert!(svg.contains(">100ms</text>"));
+        assert!(!svg.contains(">100ns</text>"));
+        assert!(!svg.contains(">1s</text>"));
+    }
+
+    #[test]
+    fn renders_empty_timings() {
+        let svg = render(&Timings::default());
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<title>").count(), 0);
+    }
+}
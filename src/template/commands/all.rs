@@ -1,5 +1,31 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::{process, time::Duration};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{all_days, answers::Answers, run_multi::run_multi, runner::Solution, Year};
+
+pub fn handle(
+    year: Option<Year>,
+    is_release: bool,
+    jobs: usize,
+    verify: bool,
+    timeout: Option<Duration>,
+    solutions: &[Solution],
+) {
+    let answers = verify.then(|| Answers::read_from_file(year));
+
+    let summary = run_multi(
+        year,
+        &all_days().collect(),
+        is_release,
+        None,
+        timeout,
+        solutions,
+        jobs,
+        answers.as_ref(),
+    );
+
+    let has_failures = !summary.failures.is_empty() || !summary.errors.is_empty();
+
+    if !summary.mismatches.is_empty() || (verify && has_failures) {
+        process::exit(1);
+    }
 }
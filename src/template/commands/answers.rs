@@ -0,0 +1,24 @@
+use std::process;
+
+use crate::template::{answers::Answers, Day, Year};
+
+pub fn handle_set(year: Option<Year>, day: Day, part: u8, answer: &str) {
+    if !(1..=day.parts()).contains(&part) {
+        let expected = if day.parts() < 2 { "1" } else { "1 or 2" };
+        eprintln!("Unexpected part `{part}`, day {day} expects {expected}.");
+        process::exit(1);
+    }
+
+    let mut answers = Answers::read_from_file(year);
+    answers.set(day, part, answer);
+
+    match answers.store_file(year) {
+        Ok(()) => {
+            println!("Stored answer for day {day}, part {part}.");
+        }
+        Err(e) => {
+            eprintln!("Failed to store answer: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -1,6 +1,10 @@
 pub mod all;
+pub mod answers;
 pub mod download;
 pub mod read;
+pub mod report;
 pub mod scaffold;
 pub mod solve;
+pub mod test;
 pub mod time;
+pub mod timings;
@@ -1,15 +1,10 @@
 use std::process;
 
-use crate::template::{aoc_cli, Day};
+use crate::template::{aoc_client, Day, Year};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
-
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+pub fn handle(year: Option<Year>, day: Day) {
+    if let Err(e) = aoc_client::read(year, day) {
+        eprintln!("failed to read day {day}: {e}");
         process::exit(1);
     };
 }
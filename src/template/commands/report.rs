@@ -0,0 +1,23 @@
+use std::{fs, process};
+
+use crate::template::{answers::Answers, report, timings::Timings, Year};
+
+static REPORT_FILE_PATH: &str = "./target/report.html";
+
+pub fn handle(year: Option<Year>, profile: Option<&str>) {
+    let timings = Timings::read_from_file(year, profile);
+    let days = report::collect(year, &timings, &Answers::read_from_file(year));
+    let html = report::render(&days, timings.total_millis());
+
+    let result = fs::create_dir_all("./target").and_then(|()| fs::write(REPORT_FILE_PATH, html));
+
+    match result {
+        Ok(()) => {
+            println!("Wrote report to {REPORT_FILE_PATH}.");
+        }
+        Err(e) => {
+            eprintln!("Failed to write report: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -1,26 +1,276 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{
+    aoc_client, bin_name, data_dir,
+    examples::{self, PartExample},
+    Day, Year,
+};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
+/// Directory of the templates that can be selected with `--template <name>`.
+const TEMPLATES_DIR: &str = "templates";
+
+/// A parse function for the `parse = parse` hook of the solution macro, which templates can include with `%PARSE_STUB%`.
+const PARSE_STUB: &str = "fn parse(input: &str) -> Vec<String> {
+    input.lines().map(String::from).collect()
+}";
+
+/// Options of the `scaffold` command.
+pub struct ScaffoldOptions {
+    /// Downloads the input and puzzle description first and fills in the examples.
+    pub download: bool,
+    /// Name of a template in `templates/`. Uses the built-in template if not set.
+    pub template: Option<String>,
+    /// The type returned by `part_one` and `part_two`.
+    pub return_type: String,
+}
+
+impl Default for ScaffoldOptions {
+    fn default() -> Self {
+        Self {
+            download: false,
+            template: None,
+            return_type: "u32".into(),
+        }
+    }
+}
+
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
+/// Opens a file for writing, keeping the contents of an existing file.
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    // the data directories of a year do not exist before its first day is scaffolded.
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+/// The expected answer as a test assertion, if it fits the return type of the parts.
+fn format_answer(example: Option<&PartExample>, return_type: &str) -> String {
+    fn fits<T: std::str::FromStr>(answer: &str) -> bool {
+        answer.parse::<T>().is_ok()
+    }
+
+    let Some(answer) = example.and_then(|x| x.answer.as_deref()) else {
+        return "None".into();
+    };
+
+    let fits = match return_type {
+        "u8" => fits::<u8>(answer),
+        "u16" => fits::<u16>(answer),
+        "u32" => fits::<u32>(answer),
+        "u64" => fits::<u64>(answer),
+        "u128" => fits::<u128>(answer),
+        "usize" => fits::<usize>(answer),
+        "i8" => fits::<i8>(answer),
+        "i16" => fits::<i16>(answer),
+        "i32" => fits::<i32>(answer),
+        "i64" => fits::<i64>(answer),
+        "i128" => fits::<i128>(answer),
+        "isize" => fits::<isize>(answer),
+        "String" => return format!("Some({answer:?}.to_string())"),
+        _ => false,
+    };
+
+    if fits {
+        format!("Some({answer})")
+    } else {
+        "None".into()
+    }
+}
+
+/// Name of the template sections that are only kept for days with a second part.
+const PART_TWO_SECTION: &str = "part_two";
+
+/// Renders the sections `{{#name}}...{{/name}}` of a template: with `keep`, only the markers are removed,
+/// otherwise the sections including their contents. A marker on its own line is removed with its line break.
+fn render_section(template: &str, name: &str, keep: bool) -> String {
+    let (start, end) = (format!("{{{{#{name}}}}}"), format!("{{{{/{name}}}}}"));
+
+    // the index after the marker at `at`, including the line break of a marker on its own line.
+    let skip_marker = |at: usize, marker: &str| -> usize {
+        let after = at + marker.len();
+        let own_line = template[..at].is_empty() || template[..at].ends_with('\n');
+        after + usize::from(own_line && template[after..].starts_with('\n'))
+    };
+
+    let mut rendered = String::new();
+    let mut pos = 0;
+
+    while let Some(from) = template[pos..].find(&start).map(|x| pos + x) {
+        rendered.push_str(&template[pos..from]);
+        pos = skip_marker(from, &start);
+
+        // an unclosed section extends to the end of the template.
+        let to = template[pos..]
+            .find(&end)
+            .map_or(template.len(), |x| pos + x);
+        if keep {
+            rendered.push_str(&template[pos..to]);
+        }
+        pos = if to < template.len() {
+            skip_marker(to, &end)
+        } else {
+            to
+        };
+    }
+
+    rendered.push_str(&template[pos..]);
+    rendered
+}
+
+/// The values that are filled into the placeholders of a module template.
+struct Module<'a> {
+    year: Option<Year>,
+    day: Day,
+    /// The title of the puzzle, if its description was downloaded.
+    title: Option<&'a str>,
+    return_type: &'a str,
+    parts: &'a [PartExample],
+    has_part_two_example: bool,
+}
+
+/// Fills in the placeholders of a module template.
+/// Solutions of a specific year declare it and read their examples from `data/<year>`.
+fn render_module(template: &str, module: &Module) -> String {
+    let Module { year, day, .. } = *module;
+
+    let (solution_args, example, example_part_two) = match year {
+        Some(year) => (
+            format!("year = {year}, {}", day.into_inner()),
+            r#"read_year_file("examples", YEAR, DAY)"#,
+            r#"read_year_file_part("examples", YEAR, DAY, 2)"#,
+        ),
+        None => (
+            day.into_inner().to_string(),
+            r#"read_file("examples", DAY)"#,
+            r#"read_file_part("examples", DAY, 2)"#,
+        ),
+    };
+
+    let part_two_example = if module.has_part_two_example {
+        example_part_two
+    } else {
+        example
+    };
+
+    let title = match module.title {
+        Some(title) => format!("Day {}: {title}", day.into_inner()),
+        None => format!("Day {}", day.into_inner()),
+    };
+
+    render_section(template, PART_TWO_SECTION, day.parts() >= 2)
+        .replace("%SOLUTION_ARGS%", &solution_args)
+        .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+        .replace("%DAY%", &day.to_string())
+        .replace(
+            "%YEAR%",
+            &year
+                .or_else(aoc_client::get_year)
+                .map(|x| x.to_string())
+                .unwrap_or_default(),
+        )
+        .replace("%TITLE%", &title)
+        .replace("%RETURN_TYPE%", module.return_type)
+        .replace("%PARSE_STUB%", PARSE_STUB)
+        .replace("%PART_ONE_EXAMPLE%", example)
+        .replace(
+            "%PART_ONE_ANSWER%",
+            &format_answer(module.parts.first(), module.return_type),
+        )
+        .replace("%PART_TWO_EXAMPLE%", part_two_example)
+        .replace(
+            "%PART_TWO_ANSWER%",
+            &format_answer(module.parts.get(1), module.return_type),
+        )
+}
+
+/// Reads the module template with the given name from `templates/<name>.txt`, or the built-in template if none is passed.
+fn read_template(name: Option<&str>) -> Result<String, std::io::Error> {
+    match name {
+        Some(name) => fs::read_to_string(Path::new(TEMPLATES_DIR).join(format!("{name}.txt"))),
+        None => Ok(MODULE_TEMPLATE.to_string()),
+    }
+}
+
+/// Creates the example file at `path`, writing `contents` unless the file already has contents.
+fn create_example_file(path: &str, contents: Option<&str>) {
+    let mut file = match create_file(path) {
+        Ok(file) => file,
+        Err(e) => {
+            eprintln!("Failed to create example file: {e}");
+            process::exit(1);
+        }
+    };
+
+    let is_empty = file.metadata().is_ok_and(|x| x.len() == 0);
+
+    match contents {
+        Some(contents) if is_empty => match file.write_all(contents.as_bytes()) {
+            Ok(()) => println!("Created example file \"{path}\" from the puzzle description"),
+            Err(e) => {
+                eprintln!("Failed to write example file: {e}");
+                process::exit(1);
+            }
+        },
+        _ => println!("Created empty example file \"{path}\""),
+    }
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Creates the module, input and example files of a day.
+/// With `download`, the input and puzzle are downloaded first and the title, examples and their answers are filled in.
+/// Passing a year creates `src/bin/<year>-<day>.rs` and keeps the data files in `data/<year>`.
+pub fn handle(year: Option<Year>, day: Day, options: &ScaffoldOptions) {
+    let data_dir = data_dir(year);
+    let path = |folder: &str, file: String| data_dir.join(folder).join(file).display().to_string();
+    let input_path = path("inputs", format!("{day}.txt"));
+    let example_path = path("examples", format!("{day}.txt"));
+    let example_path_2 = path("examples", format!("{day}-2.txt"));
+    let module_path = format!("src/bin/{}.rs", bin_name(year, day));
+
+    // fail before downloading anything if the day was already scaffolded or the template is missing.
+    if Path::new(&module_path).exists() {
+        eprintln!("Failed to create module file: \"{module_path}\" already exists");
+        process::exit(1);
+    }
+
+    let template = match read_template(options.template.as_deref()) {
+        Ok(template) => template,
+        Err(e) => {
+            let name = options.template.as_deref().unwrap_or_default();
+            eprintln!("Failed to read template \"{TEMPLATES_DIR}/{name}.txt\": {e}");
+            process::exit(1);
+        }
+    };
+
+    if options.download {
+        if let Err(e) = aoc_client::download(year, day) {
+            eprintln!("failed to download day {day}: {e}");
+            process::exit(1);
+        }
+    }
+
+    let puzzle = if options.download {
+        fs::read_to_string(aoc_client::get_puzzle_path(year, day)).ok()
+    } else {
+        None
+    };
+    let parts = puzzle.as_deref().map(examples::extract).unwrap_or_default();
+    let title = puzzle.as_deref().and_then(examples::title);
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -30,11 +280,23 @@ pub fn handle(day: Day) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    let example = parts.first().and_then(|x| x.input.as_deref());
+    // part 2 only gets its own example file if it differs from the example of part 1.
+    let example_2 = parts
+        .get(1)
+        .and_then(|x| x.input.as_deref())
+        .filter(|&x| Some(x) != example);
+
+    let module = Module {
+        year,
+        day,
+        title: title.as_deref(),
+        return_type: &options.return_type,
+        parts: &parts,
+        has_part_two_example: example_2.is_some(),
+    };
+
+    match file.write_all(render_module(&template, &module).as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -45,6 +307,7 @@ pub fn handle(day: Day) {
     }
 
     match create_file(&input_path) {
+        Ok(_) if options.download => {}
         Ok(_) => {
             println!("Created empty input file \"{}\"", &input_path);
         }
@@ -54,16 +317,161 @@ pub fn handle(day: Day) {
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
+    create_example_file(&example_path, example);
+
+    if example_2.is_some() {
+        create_example_file(&example_path_2, example_2);
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    match year {
+        Some(year) => println!("🎄 Type `cargo solve {day} --year {year}` to run your solution."),
+        None => println!("🎄 Type `cargo solve {day}` to run your solution."),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render_module, render_section, Module, MODULE_TEMPLATE};
+    use crate::{
+        day,
+        template::{examples::PartExample, Day, Year},
+        year,
+    };
+
+    fn module(
+        year: Option<Year>,
+        day: Day,
+        parts: &[PartExample],
+        has_part_two_example: bool,
+    ) -> String {
+        let module = Module {
+            year,
+            day,
+            title: None,
+            return_type: "u32",
+            parts,
+            has_part_two_example,
+        };
+        render_module(MODULE_TEMPLATE, &module)
+    }
+
+    #[test]
+    fn renders_empty_asserts() {
+        let module = module(None, day!(3), &[], false);
+        assert!(module.starts_with("advent_of_code::solution!(3);"));
+        assert_eq!(module.matches("assert_eq!(result, None);").count(), 2);
+        assert_eq!(module.matches(r#"read_file("examples", DAY)"#).count(), 2);
+    }
+
+    #[test]
+    fn renders_example_answers() {
+        let parts = [
+            PartExample {
+                input: Some("1\n".into()),
+                answer: Some("142".into()),
+            },
+            PartExample {
+                input: Some("2\n".into()),
+                answer: Some("ABC".into()),
+            },
+        ];
+        let module = module(None, day!(1), &parts, true);
+        assert!(module.contains("assert_eq!(result, Some(142));"));
+        assert!(module.contains("assert_eq!(result, None);"));
+        assert!(module.contains(
+            r#"part_two(&advent_of_code::template::read_file_part("examples", DAY, 2));"#
+        ));
+    }
+
+    #[test]
+    fn renders_year_modules() {
+        let module = module(Some(year!(2022)), day!(5), &[], true);
+        assert!(module.starts_with("advent_of_code::solution!(year = 2022, 5);"));
+        assert!(module.contains(
+            r#"part_one(&advent_of_code::template::read_year_file("examples", YEAR, DAY));"#
+        ));
+        assert!(module.contains(
+            r#"part_two(&advent_of_code::template::read_year_file_part("examples", YEAR, DAY, 2));"#
+        ));
+    }
+
+    #[test]
+    fn renders_single_part_modules() {
+        let module = module(None, day!(25), &[], false);
+        assert!(module.starts_with("advent_of_code::solution!(25);"));
+        assert!(module.contains("pub fn part_one"));
+        assert!(!module.contains("part_two"));
+        assert!(module.contains("fn test_part_one"));
+        assert!(module.ends_with("    }\n}\n"));
+        assert!(!module.contains("{{"));
+    }
+
+    #[test]
+    fn renders_part_two_sections() {
+        let template = "a\n{{#part_two}}\nb\n{{/part_two}}\nc {{#part_two}}d{{/part_two}}\n";
+        assert_eq!(render_section(template, "part_two", true), "a\nb\nc d\n");
+        assert_eq!(render_section(template, "part_two", false), "a\nc \n");
+        assert_eq!(
+            render_section("a\n{{#part_two}}\nb", "part_two", false),
+            "a\n"
+        );
+
+        let module = module(None, day!(24), &[], false);
+        assert!(module.contains("pub fn part_two"));
+        assert!(module.contains("    }\n\n    #[test]\n    fn test_part_two"));
+        assert!(!module.contains("{{"));
+    }
+
+    #[test]
+    fn renders_custom_templates() {
+        let parts = [PartExample {
+            input: None,
+            answer: Some("9000000000".into()),
+        }];
+        let module = Module {
+            year: Some(year!(2022)),
+            day: day!(7),
+            title: Some("No Space Left On Device"),
+            return_type: "u64",
+            parts: &parts,
+            has_part_two_example: false,
+        };
+        let template = "// %YEAR% %DAY% %TITLE%
+advent_of_code::solution!(%SOLUTION_ARGS%, parse = parse);
+%PARSE_STUB%
+pub fn part_one(lines: &[String]) -> Option<%RETURN_TYPE%> { None }
+// %DAY_NUMBER% %PART_ONE_ANSWER% %PART_TWO_ANSWER%
+";
+        assert_eq!(
+            render_module(template, &module),
+            "// 2022 07 Day 7: No Space Left On Device
+advent_of_code::solution!(year = 2022, 7, parse = parse);
+fn parse(input: &str) -> Vec<String> {
+    input.lines().map(String::from).collect()
+}
+pub fn part_one(lines: &[String]) -> Option<u64> { None }
+// 7 Some(9000000000) None
+"
+        );
+    }
+
+    #[test]
+    fn renders_answers_of_the_return_type() {
+        let parts = [PartExample {
+            input: None,
+            answer: Some("ABC".into()),
+        }];
+        let module = Module {
+            year: None,
+            day: day!(10),
+            title: None,
+            return_type: "String",
+            parts: &parts,
+            has_part_two_example: false,
+        };
+        let module = render_module(MODULE_TEMPLATE, &module);
+        assert!(module.contains(r#"assert_eq!(result, Some("ABC".to_string()));"#));
+        assert!(module.contains("-> Option<String>"));
+    }
 }
@@ -1,9 +1,23 @@
-use std::process::{Command, Stdio};
+use std::path::Path;
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{
+    bin_name,
+    example_tests::{print_results, run_tests},
+    run_inputs::{print_table, run_inputs},
+    run_multi::child_commands::Build,
+    runner::{InputSource, RunOptions, Solution},
+    watch::{paths_for_day, watch, POLL_INTERVAL},
+    Day, ANSI_BOLD, ANSI_RESET,
+};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Runs the binary of `day`, forwarding `options` to it.
+pub fn handle(day: Day, release: bool, dhat: bool, options: &RunOptions) {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--bin".to_string(),
+        bin_name(options.year, day),
+    ];
 
     if dhat {
         cmd_args.extend([
@@ -18,13 +32,35 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
+    if let Some(submit_part) = options.submit {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
+    if options.time {
+        cmd_args.push("--time".to_string());
+    }
+
+    match &options.input {
+        InputSource::Day => {}
+        InputSource::Path(path) => {
+            cmd_args.push("--input".to_string());
+            cmd_args.push(path.display().to_string());
+        }
+        InputSource::Stdin => {
+            cmd_args.push("--input".to_string());
+            cmd_args.push("-".to_string());
+        }
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
+        .envs(options.bench.to_env())
+        .envs(
+            options
+                .timeout
+                .map(|t| (RunOptions::TIMEOUT_ENV, t.as_millis().to_string())),
+        )
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
@@ -32,3 +68,77 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd.wait().unwrap();
 }
+
+/// Runs the example tests and then the binary of `day` whenever its module, the helpers or its data files change.
+pub fn handle_watch(day: Day, release: bool, dhat: bool, options: &RunOptions) {
+    // every save would submit the answer again.
+    if options.submit.is_some() {
+        eprintln!("`--submit` can not be combined with `--watch`.");
+        process::exit(1);
+    }
+
+    let paths = paths_for_day(options.year, day, &options.input);
+
+    watch(&paths, POLL_INTERVAL, |changed| {
+        if let Some(path) = changed.first() {
+            println!();
+            println!("🔄 {} changed.", path.display());
+        }
+
+        println!("{ANSI_BOLD}Examples{ANSI_RESET}");
+        println!("------");
+        match run_tests(options.year, day, release) {
+            Ok(results) => print_results(&results),
+            Err(e) => eprintln!("Failed to test day {day}: {e}"),
+        }
+
+        println!();
+        println!("{ANSI_BOLD}Input{ANSI_RESET}");
+        println!("------");
+        handle(day, release, dhat, options);
+
+        println!();
+        println!("👀 Watching for changes, press Ctrl-C to stop.");
+    });
+}
+
+/// Runs `day` in-process against every input in `dir` and prints a table of the results.
+/// Exits with a non-zero status if a part fails or does not match an answer file.
+pub fn handle_inputs_dir(
+    day: Day,
+    dir: &Path,
+    release: bool,
+    dhat: bool,
+    options: &RunOptions,
+    solutions: &[Solution],
+) {
+    let Some(solution) = solutions
+        .iter()
+        .find(|s| s.year == options.year && s.day == day)
+    else {
+        eprintln!("No solution found for day {day}.");
+        process::exit(1);
+    };
+
+    let build = if dhat {
+        Build::Dhat
+    } else if release {
+        Build::Release
+    } else {
+        Build::current()
+    };
+
+    let runs = match run_inputs(solution, dir, build, options) {
+        Ok(runs) => runs,
+        Err(e) => {
+            eprintln!("Failed to run inputs in {}: {e}", dir.display());
+            process::exit(1);
+        }
+    };
+
+    print_table(&runs);
+
+    if !runs.iter().all(|run| run.is_ok()) {
+        process::exit(1);
+    }
+}
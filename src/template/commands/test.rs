@@ -0,0 +1,45 @@
+use std::process;
+
+use crate::template::{
+    all_days,
+    example_tests::{print_results, print_summary, run_tests, TestStatus},
+    run_multi::{is_scaffolded, print_day_header},
+    Day, Year,
+};
+
+/// Runs the example tests of `day`, or of all scaffolded days if none is passed.
+/// Exits with a non-zero status if a test fails or does not build.
+pub fn handle(year: Option<Year>, day: Option<Day>, is_release: bool) {
+    let days: Vec<Day> = match day {
+        Some(day) => vec![day],
+        None => all_days().filter(|&day| is_scaffolded(year, day)).collect(),
+    };
+
+    let mut results = vec![];
+    let mut failed = false;
+
+    for (i, day) in days.into_iter().enumerate() {
+        print_day_header(day, i > 0);
+        match run_tests(year, day, is_release) {
+            Ok(day_results) => {
+                print_results(&day_results);
+                results.push((day, day_results));
+            }
+            Err(e) => {
+                eprintln!("Failed to test day {day}: {e}");
+                failed = true;
+            }
+        }
+    }
+
+    print_summary(&results);
+
+    let has_failures = results
+        .iter()
+        .flat_map(|(_, r)| r)
+        .any(|r| r.status == TestStatus::Failed);
+
+    if failed || has_failures {
+        process::exit(1);
+    }
+}
@@ -1,15 +1,41 @@
 use std::collections::HashSet;
+use std::process::{self, Command};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use crate::template::readme_benchmarks::{self, Comparison};
+
+pub use crate::template::readme_benchmarks::ReadmeOptions;
 use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::runner::{BenchConfig, Solution};
+use crate::template::timings::{Regression, Timings};
+use crate::template::{all_days, Day, Year, ANSI_BOLD, ANSI_RESET};
+
+pub struct TimeOptions {
+    /// Bench days that are already fully benched, too.
+    pub run_all: bool,
+    pub store: bool,
+    pub bench: BenchConfig,
+    pub timeout: Option<Duration>,
+    pub jobs: usize,
+    /// Steps that got slower than their stored timing by more than this many percent are regressions.
+    pub regression_threshold: f64,
+    pub fail_on_regression: bool,
+    /// Profile to read and store timings in, e.g. the name of the machine. `None` is `data/timings.json`.
+    pub profile: Option<String>,
+    /// Profile to compare against in an additional column of the readme table.
+    pub compare: Option<String>,
+    /// Where the benchmark table is written when storing timings.
+    pub readme: ReadmeOptions,
+    /// Year to bench, timings are then stored in `data/<year>`.
+    pub year: Option<Year>,
+}
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+pub fn handle(day: Option<Day>, options: &TimeOptions, solutions: &[Solution]) {
+    let stored_timings = Timings::read_from_file(options.year, options.profile.as_deref());
 
     let days_to_run = day.map_or_else(
         || {
-            if run_all {
+            if options.run_all {
                 all_days().collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
@@ -21,20 +47,92 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(
+        options.year,
+        &days_to_run,
+        true,
+        Some(&options.bench),
+        options.timeout,
+        solutions,
+        options.jobs,
+        None,
+    )
+    .timings
+    .unwrap();
+
+    let regressions = stored_timings.regressions(&timings, options.regression_threshold);
+    print_regressions(&regressions, options.regression_threshold);
+
+    if options.store {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |x| x.as_secs());
+        let timings = timings.stamp(timestamp, git_commit().as_deref());
 
-    if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings
+            .store_file(options.year, options.profile.as_deref())
+            .unwrap();
+
+        let compare_timings = options
+            .compare
+            .as_deref()
+            .map(|name| (name, Timings::read_from_file(options.year, Some(name))));
+        let comparison = compare_timings
+            .as_ref()
+            .map(|(name, timings)| Comparison { name, timings });
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        let readme = options.readme.for_profile(options.profile.as_deref());
+        match readme_benchmarks::update(merged_timings, comparison.as_ref(), &readme) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+            Err(e) => {
+                eprintln!("Failed to store updated benchmarks: {e}");
             }
         }
     }
+
+    if options.fail_on_regression && !regressions.is_empty() {
+        process::exit(1);
+    }
+}
+
+fn print_regressions(regressions: &[Regression], threshold: f64) {
+    if regressions.is_empty() {
+        return;
+    }
+
+    println!();
+    println!(
+        "{ANSI_BOLD}Regressions:{ANSI_RESET} {} steps got more than {threshold}% slower than their stored timing.",
+        regressions.len()
+    );
+
+    for regression in regressions {
+        println!(
+            "Day {} {}: {:.1?} -> {:.1?} ({ANSI_BOLD}{:+.1}%{ANSI_RESET})",
+            regression.day,
+            regression.step,
+            regression.previous,
+            regression.current,
+            regression.percent()
+        );
+    }
+}
+
+/// The abbreviated hash of the checked out commit, `None` outside of a git repository.
+fn git_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let commit = String::from_utf8(output.stdout).ok()?;
+    Some(commit.trim().to_string()).filter(|x| !x.is_empty())
 }
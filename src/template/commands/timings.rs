@@ -0,0 +1,61 @@
+use std::process;
+
+use crate::template::{
+    readme_benchmarks::{self, Comparison, ReadmeOptions},
+    timings::Timings,
+    timings_diff::{diff, print_diff},
+    timings_export::export,
+    Year,
+};
+
+pub use crate::template::timings_export::{ExportFormat, ExportOptions, SortBy};
+
+/// Exits with an error if no timings were stored for `profile`.
+fn require_profile(year: Option<Year>, profile: &str) {
+    let path = Timings::file_path(year, Some(profile));
+    if !path.exists() {
+        eprintln!("Profile `{profile}` does not exist, expected timings at {path:?}.");
+        process::exit(1);
+    }
+}
+
+pub fn handle_export(year: Option<Year>, profile: Option<&str>, options: &ExportOptions) {
+    if let Some(profile) = profile {
+        require_profile(year, profile);
+    }
+
+    let timings = Timings::read_from_file(year, profile);
+    println!("{}", export(&timings, options));
+}
+
+/// Prints the difference of each step between the profiles `a` and `b`.
+/// With `readme`, the benchmark table is updated with the timings of `b` and a column comparing them to `a`.
+pub fn handle_diff(year: Option<Year>, a: &str, b: &str, readme: Option<&ReadmeOptions>) {
+    require_profile(year, a);
+    require_profile(year, b);
+
+    let (timings_a, timings_b) = (
+        Timings::read_from_file(year, Some(a)),
+        Timings::read_from_file(year, Some(b)),
+    );
+
+    print_diff(&diff(&timings_a, &timings_b), a, b);
+
+    if let Some(readme) = readme {
+        let comparison = Comparison {
+            name: a,
+            timings: &timings_a,
+        };
+
+        println!();
+        match readme_benchmarks::update(timings_b, Some(&comparison), readme) {
+            Ok(()) => {
+                println!("Stored updated benchmarks.");
+            }
+            Err(e) => {
+                eprintln!("Failed to store updated benchmarks: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
@@ -41,6 +41,15 @@ impl Day {
     pub fn into_inner(self) -> u8 {
         self.0
     }
+
+    /// Number of parts of the puzzle, day 25 only has a single part.
+    pub fn parts(self) -> u8 {
+        if self.0 == 25 {
+            1
+        } else {
+            2
+        }
+    }
 }
 
 #[cfg(feature = "today")]
@@ -0,0 +1,358 @@
+/// Module that runs the example tests of a solution binary and collects their results per part.
+use std::{
+    fmt::Display,
+    fs, io,
+    process::{Command, Stdio},
+};
+
+use crate::template::{bin_name, data_dir, Day, Year, ANSI_BOLD, ANSI_RESET};
+
+use super::run_multi::get_path_for_bin;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TestStatus {
+    Passed,
+    Failed,
+    Ignored,
+}
+
+/// Result of one test of a solution, e.g. `tests::test_part_one`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TestResult {
+    pub name: String,
+    pub status: TestStatus,
+    /// Values of a failed `assert_eq!(result, <expected>)`.
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+    /// Panic message of a failed test that is not an assertion of two values.
+    pub message: Option<String>,
+    /// The example file the test reads.
+    pub example: Option<String>,
+}
+
+impl TestResult {
+    /// The part a test belongs to, for tests named `test_part_one` or `test_part_two`.
+    pub fn part(&self) -> Option<u8> {
+        match self.name.rsplit("::").next()? {
+            "test_part_one" => Some(1),
+            "test_part_two" => Some(2),
+            _ => None,
+        }
+    }
+
+    /// `Part 1` for the tests of a part, the name of the test otherwise.
+    pub fn label(&self) -> String {
+        match self.part() {
+            Some(part) => format!("Part {part}"),
+            None => self.name.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    IO(io::Error),
+    /// The tests did not compile, the compiler output is forwarded to stderr.
+    BuildFailed,
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::IO(e) => write!(f, "{e}"),
+            Error::BuildFailed => write!(f, "tests could not be built."),
+        }
+    }
+}
+
+/// Runs the tests of the solution binary of a day.
+/// Returns no results for days that have not been scaffolded yet.
+pub fn run_tests(year: Option<Year>, day: Day, is_release: bool) -> Result<Vec<TestResult>, Error> {
+    let module_path = get_path_for_bin(year, day);
+    let Ok(source) = fs::read_to_string(&module_path) else {
+        return Ok(vec![]);
+    };
+
+    let bin = bin_name(year, day);
+    // NOTE: `--quiet` would switch the test harness to its terse output.
+    let mut args = vec!["test", "--bin", &bin];
+
+    if is_release {
+        args.push("--release");
+    }
+
+    args.extend(["--", "--color", "never"]);
+
+    // the output of the test harness is parsed, compiler output is only forwarded if the build fails.
+    let output = Command::new("cargo")
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()?;
+
+    let mut results = parse_output(&String::from_utf8_lossy(&output.stdout));
+
+    if results.is_empty() && !output.status.success() {
+        eprint!("{}", String::from_utf8_lossy(&output.stderr));
+        return Err(Error::BuildFailed);
+    }
+
+    let examples_dir = data_dir(year).join("examples");
+    for result in &mut results {
+        result.example = example_file(&source, &result.name).map(|part| {
+            let file = match part {
+                Some(part) => format!("{day}-{part}.txt"),
+                None => format!("{day}.txt"),
+            };
+            examples_dir.join(file).display().to_string()
+        });
+    }
+
+    Ok(results)
+}
+
+/// Parses the output of the libtest harness into one result per test.
+fn parse_output(stdout: &str) -> Vec<TestResult> {
+    let mut results: Vec<TestResult> = stdout
+        .lines()
+        .filter_map(|line| {
+            let (name, status) = line.strip_prefix("test ")?.rsplit_once(" ... ")?;
+            let status = match status {
+                "ok" => TestStatus::Passed,
+                "FAILED" => TestStatus::Failed,
+                x if x.starts_with("ignored") => TestStatus::Ignored,
+                _ => return None,
+            };
+            Some(TestResult {
+                name: name.to_string(),
+                status,
+                expected: None,
+                actual: None,
+                message: None,
+                example: None,
+            })
+        })
+        .collect();
+
+    // failed tests print their panic in a section headed by `---- <name> stdout ----`.
+    for section in stdout.split("\n---- ").skip(1) {
+        let Some((header, body)) = section.split_once('\n') else {
+            continue;
+        };
+        let Some(name) = header.strip_suffix(" stdout ----") else {
+            continue;
+        };
+        let Some(result) = results.iter_mut().find(|r| r.name == name) else {
+            continue;
+        };
+
+        let mut message = vec![];
+        let mut lines = body
+            .lines()
+            .skip_while(|line| !line.contains("panicked at"));
+        lines.next();
+
+        for line in lines {
+            // the message ends before the backtrace, which is printed with `RUST_BACKTRACE` set.
+            if line.is_empty()
+                || line.starts_with("note: ")
+                || line == "stack backtrace:"
+                || line == "failures:"
+            {
+                break;
+            }
+            // `assert_eq!(result, <expected>)` prints the result left and the expectation right.
+            if let Some(actual) = line.strip_prefix("  left: ") {
+                result.actual = Some(actual.to_string());
+            } else if let Some(expected) = line.strip_prefix(" right: ") {
+                result.expected = Some(expected.to_string());
+            } else {
+                message.push(line);
+            }
+        }
+
+        if result.expected.is_none() && !message.is_empty() {
+            result.message = Some(message.join("\n"));
+        }
+    }
+
+    results
+}
+
+/// Finds the example file read by a test in the module source.
+/// Returns `Some(None)` for the example of the day and `Some(Some(part))` for the example of a part.
+fn example_file(source: &str, test_name: &str) -> Option<Option<u8>> {
+    let function = test_name.rsplit("::").next()?;
+    let (_, body) = source.split_once(&format!("fn {function}("))?;
+    let body = body.split("fn ").next().unwrap_or(body);
+
+    let (_, args) = body.split_once(r#""examples","#)?;
+    let (args, _) = args.split_once(')')?;
+
+    // `read_file_part("examples", DAY, 2)` passes the part as last argument, after the day.
+    // long calls are wrapped by rustfmt, with a trailing comma.
+    Some(
+        args.rsplit(',')
+            .map(str::trim)
+            .find(|x| !x.is_empty())
+            .and_then(|part| part.parse().ok()),
+    )
+}
+
+/// Prints the result of each test of a day, with the expected and actual value of failed assertions.
+pub fn print_results(results: &[TestResult]) {
+    if results.is_empty() {
+        println!("No tests.");
+        return;
+    }
+
+    for result in results {
+        let example = result
+            .example
+            .as_deref()
+            .map(|x| format!(" ({x})"))
+            .unwrap_or_default();
+
+        match result.status {
+            TestStatus::Passed => println!("{}: ✔{example}", result.label()),
+            TestStatus::Ignored => println!("{}: ignored{example}", result.label()),
+            TestStatus::Failed => {
+                println!(
+                    "{}: ✖ {ANSI_BOLD}failed{ANSI_RESET}{example}",
+                    result.label()
+                );
+                print_failure(result);
+            }
+        }
+    }
+}
+
+fn print_failure(result: &TestResult) {
+    if let (Some(expected), Some(actual)) = (&result.expected, &result.actual) {
+        println!("  expected: {ANSI_BOLD}{expected}{ANSI_RESET}");
+        println!("  actual:   {ANSI_BOLD}{actual}{ANSI_RESET}");
+    } else if let Some(message) = &result.message {
+        message.lines().for_each(|line| println!("  {line}"));
+    }
+}
+
+/// Prints the failed tests of all days, in the style of the summary of `cargo all`.
+pub fn print_summary(results: &[(Day, Vec<TestResult>)]) {
+    let tests = results
+        .iter()
+        .flat_map(|(day, r)| r.iter().map(move |r| (day, r)));
+    let total = tests.clone().count();
+    let failed: Vec<_> = tests
+        .filter(|(_, r)| r.status == TestStatus::Failed)
+        .collect();
+
+    println!();
+
+    if failed.is_empty() {
+        println!("{ANSI_BOLD}Passed:{ANSI_RESET} all {total} example tests pass.");
+        return;
+    }
+
+    println!(
+        "{ANSI_BOLD}Failed:{ANSI_RESET} {} of {total} example tests failed.",
+        failed.len()
+    );
+
+    for (day, result) in failed {
+        print!("Day {day} {}", result.label());
+        match (&result.expected, &result.actual) {
+            (Some(expected), Some(actual)) => println!(
+                ": expected {ANSI_BOLD}{expected}{ANSI_RESET}, got {ANSI_BOLD}{actual}{ANSI_RESET}"
+            ),
+            _ => println!(),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{example_file, parse_output, TestStatus};
+
+    const OUTPUT: &str = "
+running 3 tests
+test tests::test_part_one ... ok
+test tests::test_part_two ... FAILED
+test tests::test_parse ... FAILED
+
+failures:
+
+---- tests::test_part_two stdout ----
+
+thread 'tests::test_part_two' (21834) panicked at src/bin/01.rs:31:9:
+assertion `left == right` failed
+  left: None
+ right: Some(281)
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+---- tests::test_parse stdout ----
+
+thread 'tests::test_parse' (21835) panicked at src/bin/01.rs:40:9:
+not yet implemented
+stack backtrace:
+   0: __rustc::rust_begin_unwind
+
+
+failures:
+    tests::test_part_two
+    tests::test_parse
+
+test result: FAILED. 1 passed; 2 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.05s
+";
+
+    #[test]
+    fn parses_test_results() {
+        let results = parse_output(OUTPUT);
+        assert_eq!(results.len(), 3);
+
+        assert_eq!(results[0].part(), Some(1));
+        assert_eq!(results[0].status, TestStatus::Passed);
+        assert_eq!(results[0].expected, None);
+
+        assert_eq!(results[1].part(), Some(2));
+        assert_eq!(results[1].status, TestStatus::Failed);
+        assert_eq!(results[1].expected.as_deref(), Some("Some(281)"));
+        assert_eq!(results[1].actual.as_deref(), Some("None"));
+
+        assert_eq!(results[2].label(), "tests::test_parse");
+        assert_eq!(results[2].message.as_deref(), Some("not yet implemented"));
+    }
+
+    #[test]
+    fn finds_example_files() {
+        let source = r#"
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+    }
+
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_year_file_part("examples", YEAR, DAY, 2));
+    }
+
+    fn test_part_three() {
+        let result = part_two(&advent_of_code::template::read_file_part(
+            "examples", DAY, 3,
+        ));
+    }
+
+    fn test_helper() {}
+"#;
+        assert_eq!(example_file(source, "tests::test_part_one"), Some(None));
+        assert_eq!(example_file(source, "tests::test_part_two"), Some(Some(2)));
+        assert_eq!(
+            example_file(source, "tests::test_part_three"),
+            Some(Some(3))
+        );
+        assert_eq!(example_file(source, "tests::test_helper"), None);
+    }
+}
@@ -0,0 +1,123 @@
+//! Module that extracts the title, examples and their expected answers from a downloaded puzzle description.
+
+/// The example of one part of a puzzle.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PartExample {
+    /// The first code block of the part, if it has one. Part 2 usually reuses the example of part 1.
+    pub input: Option<String>,
+    /// The last emphasized code span of the part, which holds the answer to the example.
+    pub answer: Option<String>,
+}
+
+/// Splits the puzzle markdown into its parts and extracts one example per part.
+/// Every part starts with a `## ` heading, e.g. `## --- Part Two ---`.
+pub fn extract(markdown: &str) -> Vec<PartExample> {
+    let mut sections: Vec<&str> = vec![];
+    let mut rest = markdown;
+
+    while let Some(start) = find_heading(rest) {
+        let section = &rest[start..];
+        let end = find_heading(&section[3..]).map_or(section.len(), |x| x + 3);
+        sections.push(&section[..end]);
+        rest = &section[end..];
+    }
+
+    sections
+        .into_iter()
+        .map(|section| PartExample {
+            input: find_code_block(section),
+            answer: find_answer(section),
+        })
+        .collect()
+}
+
+/// Extracts the title of the puzzle from its first heading, e.g. `## --- Day 1: Trebuchet?! ---`.
+pub fn title(markdown: &str) -> Option<String> {
+    let heading = &markdown[find_heading(markdown)?..];
+    let heading = heading.lines().next()?.strip_prefix("## --- ")?;
+    let heading = heading.strip_suffix(" ---").unwrap_or(heading);
+    let (_, title) = heading.split_once(": ")?;
+    Some(title.trim().to_string()).filter(|x| !x.is_empty())
+}
+
+fn find_heading(s: &str) -> Option<usize> {
+    if s.starts_with("## ") {
+        Some(0)
+    } else {
+        s.find("\n## ").map(|x| x + 1)
+    }
+}
+
+fn find_code_block(section: &str) -> Option<String> {
+    let (_, rest) = section.split_once("```\n")?;
+    let (block, _) = rest.split_once("```")?;
+    Some(block.to_string())
+}
+
+fn find_answer(section: &str) -> Option<String> {
+    // `<code><em>x</em></code>` converts to "`*x*`", `<em><code>x</code></em>` to "*`x`*".
+    ["`*", "*`"].into_iter().find_map(|open| {
+        let close: String = open.chars().rev().collect();
+        let (_, rest) = section.rsplit_once(open)?;
+        let (answer, _) = rest.split_once(close.as_str())?;
+        Some(answer.to_string()).filter(|x| !x.is_empty() && !x.contains('\n'))
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract, title, PartExample};
+
+    const PUZZLE: &str = "## --- Day 1: Trebuchet?! ---
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+Adding these together produces `*142*`.
+
+## --- Part Two ---
+
+For example:
+
+```
+two1nine
+```
+
+Adding these together produces *`281`*.
+";
+
+    #[test]
+    fn extracts_examples_per_part() {
+        assert_eq!(
+            extract(PUZZLE),
+            vec![
+                PartExample {
+                    input: Some("1abc2\npqr3stu8vwx\n".into()),
+                    answer: Some("142".into()),
+                },
+                PartExample {
+                    input: Some("two1nine\n".into()),
+                    answer: Some("281".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn handles_missing_examples() {
+        let parts = extract("## --- Day 2: Cube Conundrum ---\n\nNo example here.\n");
+        assert_eq!(parts, vec![PartExample::default()]);
+        assert!(extract("").is_empty());
+    }
+
+    #[test]
+    fn extracts_titles() {
+        assert_eq!(title(PUZZLE).as_deref(), Some("Trebuchet?!"));
+        assert_eq!(title("## --- Part Two ---\n"), None);
+        assert_eq!(title(""), None);
+    }
+}
@@ -1,68 +1,193 @@
-use std::{env, fs};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
 
-pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
 pub mod runner;
 
 pub use day::*;
+pub use year::*;
 
+mod answers;
+mod benchmark_chart;
 mod day;
+mod example_tests;
+mod examples;
 mod readme_benchmarks;
+mod report;
+mod run_inputs;
 mod run_multi;
+mod submissions;
 mod timings;
+mod timings_diff;
+mod timings_export;
+mod watch;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// Directory of the data files, `data/<year>` when working on a specific year.
+#[must_use]
+pub fn data_dir(year: Option<Year>) -> PathBuf {
+    match year {
+        Some(year) => Path::new("data").join(year.to_string()),
+        None => PathBuf::from("data"),
+    }
+}
+
+/// Name of the solution binary of a day, `<year>-<day>` when working on a specific year.
+#[must_use]
+pub fn bin_name(year: Option<Year>, day: Day) -> String {
+    match year {
+        Some(year) => format!("{year}-{day}"),
+        None => day.to_string(),
+    }
+}
+
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
+    read_year_file(folder, None, day)
+}
+
+/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+#[must_use]
+pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+    read_year_file_part(folder, None, day, part)
+}
+
+/// Like [`read_file`], reading from `data/<year>` if a year is passed.
+#[must_use]
+pub fn read_year_file(folder: &str, year: Option<Year>, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd
+        .join(data_dir(year))
+        .join(folder)
+        .join(format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+/// Like [`read_file_part`], reading from `data/<year>` if a year is passed.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_year_file_part(folder: &str, year: Option<Year>, day: Day, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(data_dir(year))
         .join(folder)
         .join(format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY` and `YEAR` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Day 25 only has a single part, `solution!(25)` runs part one only.
+///
+/// Passing `parse = <function>` parses the input once, times it separately from the parts,
+/// and passes a reference to its output to each part instead of the raw input.
+/// The output of the parse function has to own its data, it can not borrow from the input.
+///
+/// Prefixing the day with `year = <year>` creates the constant `YEAR` and reads the data of
+/// the day from `data/<year>`, for solutions in workspaces that keep several years.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    (year = $year:literal, $($rest:tt)*) => {
+        $crate::solution!(@year ::core::option::Option::Some($crate::year!($year)), $($rest)*);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+
+    // NOTE: day 25 only has a single part.
+    (@year $year:expr, 25) => {
+        $crate::solution!(@impl $year, 25, [part_one, 1]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    (@year $year:expr, 25, parse = $parse:expr) => {
+        $crate::solution!(@impl $year, 25, parse = $parse, [part_one, 1]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@year $year:expr, $day:expr) => {
+        $crate::solution!(@impl $year, $day, [part_one, 1] [part_two, 2]);
+    };
+    (@year $year:expr, $day:expr, 1) => {
+        $crate::solution!(@impl $year, $day, [part_one, 1]);
+    };
+    (@year $year:expr, $day:expr, 2) => {
+        $crate::solution!(@impl $year, $day, [part_two, 2]);
+    };
+    (@year $year:expr, $day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl $year, $day, parse = $parse, [part_one, 1] [part_two, 2]);
+    };
+    (@year $year:expr, $day:expr, parse = $parse:expr, 1) => {
+        $crate::solution!(@impl $year, $day, parse = $parse, [part_one, 1]);
+    };
+    (@year $year:expr, $day:expr, parse = $parse:expr, 2) => {
+        $crate::solution!(@impl $year, $day, parse = $parse, [part_two, 2]);
+    };
+
+    (@impl $year:expr, $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@setup $year, $day);
+
+        /// Runs all parts against `input`. Used by `main` and the in-process solution registry.
+        #[doc(hidden)]
+        pub fn __run(
+            input: &str,
+            options: &$crate::template::runner::RunOptions,
+        ) -> Vec<$crate::template::runner::Record> {
+            use $crate::template::runner::*;
+            let input: ::std::sync::Arc<str> = input.into();
+            vec![$( run_part($func, input.clone(), DAY, $part, options), )*]
+        }
+    };
+    (@impl $year:expr, $day:expr, parse = $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@setup $year, $day);
+
+        /// Parses `input` and runs all parts against it. Used by `main` and the in-process solution registry.
+        #[doc(hidden)]
+        pub fn __run(
+            input: &str,
+            options: &$crate::template::runner::RunOptions,
+        ) -> Vec<$crate::template::runner::Record> {
+            use $crate::template::runner::*;
+            let (parsed, parse_record) = run_parse($parse, input.into(), options);
+            let Some(parsed) = parsed else {
+                return vec![parse_record];
+            };
+            let parsed = ::std::sync::Arc::new(parsed);
+            vec![parse_record, $( run_part($func, parsed.clone(), DAY, $part, options), )*]
+        }
+    };
+
+    (@setup $year:expr, $day:expr) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The year of the current day, `None` unless declared with `year = <year>`.
+        const YEAR: ::core::option::Option<$crate::template::Year> = $year;
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        // NOTE: unused when the solution is compiled into the solution registry of the main binary.
+        #[allow(dead_code)]
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let options = $crate::template::runner::RunOptions {
+                year: YEAR,
+                ..$crate::template::runner::RunOptions::from_args()
+            };
+            let input = options.input.read(YEAR, DAY).unwrap_or_else(|e| {
+                eprintln!("could not read input from {}: {e}", options.input);
+                std::process::exit(1);
+            });
+            __run(&input, &options);
         }
     };
+
+    ($($rest:tt)*) => {
+        $crate::solution!(@year ::core::option::Option::None, $($rest)*);
+    };
 }
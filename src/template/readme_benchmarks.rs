@@ -1,9 +1,15 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::benchmark_chart;
+use crate::template::timings::{Timings, DEFAULT_PROFILE};
+use crate::template::timings_diff::format_total_delta;
+use crate::template::{bin_name, Day, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -19,139 +25,299 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Where and how the benchmark table is written.
+#[derive(Clone, Debug)]
+pub struct ReadmeOptions {
+    /// The markdown file that contains the table.
+    pub file: PathBuf,
+    /// Name of the table, to keep several tables in one file, e.g. `release` for `<!--- benchmarking table:release --->`.
+    pub table: Option<String>,
+    /// Markdown heading prefix of the table, e.g. `##`.
+    pub heading: String,
+    /// Render a bar chart of the timings and embed it below the table.
+    pub chart: bool,
+    /// Year of the timings, days then link to `src/bin/<year>-<day>.rs`.
+    pub year: Option<Year>,
+}
+
+impl Default for ReadmeOptions {
+    fn default() -> Self {
+        ReadmeOptions {
+            file: "README.md".into(),
+            table: None,
+            heading: "##".into(),
+            chart: false,
+            year: None,
+        }
+    }
+}
+
+impl ReadmeOptions {
+    /// The options to write the timings of `profile` with. Timings of a profile other than the default one
+    /// are written to the table named after the profile, unless a table is set explicitly.
+    pub fn for_profile(&self, profile: Option<&str>) -> Self {
+        let profile = profile.filter(|x| *x != DEFAULT_PROFILE);
+        ReadmeOptions {
+            table: self.table.clone().or_else(|| profile.map(Into::into)),
+            ..self.clone()
+        }
+    }
+
+    /// The comment that surrounds the table, e.g. `<!--- benchmarking table:release --->` for named tables.
+    fn marker(&self) -> String {
+        match &self.table {
+            Some(name) => MARKER.replace(" --->", &format!(":{name} --->")),
+            None => MARKER.into(),
+        }
+    }
+
+    /// Path of the chart relative to the file, e.g. `.assets/benchmarks-release.svg` for named tables.
+    fn chart_path(&self) -> String {
+        match &self.table {
+            Some(name) => format!(".assets/benchmarks-{name}.svg"),
+            None => ".assets/benchmarks.svg".into(),
+        }
+    }
+}
+
+/// Timings to compare against in an additional column of the table, e.g. those of another machine.
+pub struct Comparison<'a> {
+    pub name: &'a str,
+    pub timings: &'a Timings,
+}
+
 pub struct TablePosition {
     pos_start: usize,
     pos_end: usize,
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(year: Option<Year>, day: Day) -> String {
+    format!("./src/bin/{}.rs", bin_name(year, day))
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+        return Err(Error::Parser(format!(
+            "{marker}: too many occurences of marker in README."
+        )));
     }
 
     let pos_start = matches
         .first()
         .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+        .ok_or_else(|| Error::Parser(format!("{marker}: could not find table start position.")))?;
 
     let pos_end = matches
         .last()
         .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+        .ok_or_else(|| Error::Parser(format!("{marker}: could not find table end position.")))?;
 
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn construct_table(
+    options: &ReadmeOptions,
+    timings: Timings,
+    total_millis: f64,
+    comparison: Option<&Comparison>,
+) -> String {
+    let marker = options.marker();
+    let header = match &options.table {
+        Some(name) => format!("{} Benchmarks ({name})", options.heading),
+        None => format!("{} Benchmarks", options.heading),
+    };
+
+    let (comparison_header, comparison_align) = match comparison {
+        Some(c) => (format!(" vs. {} |", c.name), " :---: |"),
+        None => (String::new(), ""),
+    };
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        marker.clone(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("| Day | Parse | Part 1 | Part 2 |{comparison_header}"),
+        format!("| :---: | :---: | :---: | :---:  |{comparison_align}"),
     ];
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+    for timing in &timings.data {
+        let path = get_path_for_bin(options.year, timing.day);
+        let comparison_cell = comparison.map_or_else(String::new, |c| {
+            format!(
+                " `{}` |",
+                format_total_delta(c.timings, &timings, timing.day)
+            )
+        });
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | `{}` | `{}` | `{}` |{}",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            timing
+                .parse
+                .map_or_else(|| "-".into(), |x| format!("{:.1?}", x.duration)),
+            timing
+                .part_1
+                .map_or_else(|| "-".into(), |x| format!("{:.1?}", x.duration)),
+            timing.part_2.map_or_else(
+                // days without a second part are complete after part 1.
+                || if timing.day.parts() < 2 { "n/a" } else { "-" }.into(),
+                |x| format!("{:.1?}", x.duration)
+            ),
+            comparison_cell
         ));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    if options.chart {
+        lines.push(String::new());
+        lines.push(format!("![Benchmark chart](./{})", options.chart_path()));
+    }
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    comparison: Option<&Comparison>,
+    options: &ReadmeOptions,
+) -> Result<(), Error> {
+    let positions = locate_table(s, &options.marker())?;
+    let table = construct_table(options, timings, total_millis, comparison);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
+/// Writes the benchmark table to the file, with an additional column comparing the total time of each day to `comparison`.
+/// With `options.chart`, or if the table already embeds it, a bar chart of the timings is rendered to `.assets/` next to the file and embedded below the table.
+pub fn update(
+    timings: Timings,
+    comparison: Option<&Comparison>,
+    options: &ReadmeOptions,
+) -> Result<(), Error> {
+    let path = &options.file;
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+
+    let options = ReadmeOptions {
+        chart: options.chart || has_chart(&readme, options)?,
+        ..options.clone()
+    };
+
+    if options.chart {
+        let chart_path = path
+            .parent()
+            .unwrap_or(Path::new(""))
+            .join(options.chart_path());
+        if let Some(dir) = chart_path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(chart_path, benchmark_chart::render(&timings))?;
+    }
+
+    update_content(&mut readme, timings, total_millis, comparison, &options)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
+/// Whether the current benchmark table embeds the chart.
+fn has_chart(readme: &str, options: &ReadmeOptions) -> Result<bool, Error> {
+    let positions = locate_table(readme, &options.marker())?;
+    Ok(readme[positions.pos_start..positions.pos_end].contains(&options.chart_path()))
+}
+
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
-
-    fn get_mock_timings() -> Timings {
-        Timings {
-            data: vec![
-                Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
-                },
-                Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
-                },
-                Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
-                },
-            ],
-        }
+    use super::{has_chart, locate_table, update_content, Comparison, ReadmeOptions, MARKER};
+
+    use crate::{
+        day,
+        template::timings::test_helpers::{get_mock_timings, step, timing},
+    };
+
+    #[test]
+    fn locates_table_in_readme() {
+        let readme = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"));
+        let position = locate_table(readme, MARKER).unwrap();
+        assert!(readme[position.pos_start..position.pos_end].contains("| Day |"));
     }
 
     #[test]
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            None,
+            &ReadmeOptions::default(),
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            None,
+            &ReadmeOptions::default(),
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            None,
+            &ReadmeOptions::default(),
+        )
+        .unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            None,
+            &ReadmeOptions::default(),
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            None,
+            &ReadmeOptions::default(),
+        )
+        .unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -159,18 +325,25 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            None,
+            &ReadmeOptions::default(),
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `-` | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `5.0ms` | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `-` | `40.0ms` | `-` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -179,4 +352,139 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_comparison() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let mut other = get_mock_timings();
+        other.data.remove(1);
+        other.data[0].part_1 = step(20_000);
+
+        let comparison = Comparison {
+            name: "laptop",
+            timings: &other,
+        };
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            Some(&comparison),
+            &ReadmeOptions::default(),
+        )
+        .unwrap();
+
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(lines[3], "| Day | Parse | Part 1 | Part 2 | vs. laptop |");
+        assert_eq!(lines[4], "| :---: | :---: | :---: | :---:  | :---: |");
+        assert_eq!(
+            lines[5],
+            "| [Day 1](./src/bin/01.rs) | `-` | `10.0ms` | `20.0ms` | `-10.0ms (-25.0%)` |"
+        );
+        assert_eq!(
+            lines[6],
+            "| [Day 2](./src/bin/02.rs) | `5.0ms` | `30.0ms` | `40.0ms` | `-` |"
+        );
+        assert_eq!(
+            lines[7],
+            "| [Day 4](./src/bin/04.rs) | `-` | `40.0ms` | `-` | `+0.0ns (+0.0%)` |"
+        );
+    }
+
+    #[test]
+    fn embeds_chart() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let options = ReadmeOptions {
+            chart: true,
+            ..ReadmeOptions::default()
+        };
+        update_content(&mut s, get_mock_timings(), 190.0, None, &options).unwrap();
+        assert!(s.ends_with(
+            "**Total: 190.00ms**\n\n![Benchmark chart](./.assets/benchmarks.svg)\n<!--- benchmarking table --->"
+        ));
+        assert_eq!(has_chart(&s, &ReadmeOptions::default()).unwrap(), true);
+    }
+
+    #[test]
+    fn updates_named_tables() {
+        let release = ReadmeOptions {
+            table: Some("release".into()),
+            heading: "###".into(),
+            ..ReadmeOptions::default()
+        };
+        let debug = ReadmeOptions {
+            table: Some("debug".into()),
+            ..release.clone()
+        };
+
+        let mut s = [
+            "<!--- benchmarking table:release --->",
+            "<!--- benchmarking table:release --->",
+            "<!--- benchmarking table:debug --->",
+            "<!--- benchmarking table:debug --->",
+        ]
+        .join("\n");
+
+        update_content(&mut s, get_mock_timings(), 190.0, None, &release).unwrap();
+        update_content(&mut s, get_mock_timings(), 380.0, None, &debug).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None, &release).unwrap();
+
+        assert_eq!(s.matches("### Benchmarks (release)").count(), 1);
+        assert_eq!(s.matches("### Benchmarks (debug)").count(), 1);
+        assert_eq!(s.matches("**Total: 380.00ms**").count(), 1);
+        assert_eq!(
+            s.matches("<!--- benchmarking table:release --->").count(),
+            2
+        );
+        assert!(s.find("(release)").unwrap() < s.find("(debug)").unwrap());
+    }
+
+    #[test]
+    fn writes_profiles_to_their_own_table() {
+        let options = ReadmeOptions::default();
+        assert_eq!(options.for_profile(None).table, None);
+        assert_eq!(options.for_profile(Some("default")).table, None);
+
+        let host = options.for_profile(Some("hostB"));
+        assert_eq!(host.table.as_deref(), Some("hostB"));
+
+        let release = ReadmeOptions {
+            table: Some("release".into()),
+            ..ReadmeOptions::default()
+        };
+        assert_eq!(
+            release.for_profile(Some("hostB")).table.as_deref(),
+            Some("release")
+        );
+
+        // the default table is left alone if the readme has no table for the profile.
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), 190.0, None, &options).unwrap();
+        let before = s.clone();
+        assert!(update_content(&mut s, get_mock_timings(), 0.0, None, &host).is_err());
+        assert_eq!(s, before);
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_named_marker_not_present() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let options = ReadmeOptions {
+            table: Some("release".into()),
+            ..ReadmeOptions::default()
+        };
+        update_content(&mut s, get_mock_timings(), 190.0, None, &options).unwrap();
+    }
+
+    #[test]
+    fn marks_missing_parts_of_single_part_days() {
+        let mut timings = get_mock_timings();
+        timings
+            .data
+            .push(timing(day!(25), None, step(60_000), None));
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 250.0, None, &ReadmeOptions::default()).unwrap();
+        assert!(s.contains("| [Day 25](./src/bin/25.rs) | `-` | `60.0ms` | `n/a` |"));
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `-` | `10.0ms` | `20.0ms` |"));
+    }
 }
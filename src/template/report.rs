@@ -0,0 +1,200 @@
+/// Module that renders a static HTML report of the progress and benchmarks of all days.
+use std::{fmt::Write, path::Path};
+
+use crate::template::{
+    all_days,
+    answers::Answers,
+    aoc_client::get_puzzle_path,
+    run_multi::get_path_for_bin,
+    timings::{StepTiming, Timing, Timings},
+    Day, Year,
+};
+
+/// Everything the report shows about a single day.
+#[derive(Clone, Debug)]
+pub struct DayReport {
+    pub year: Option<Year>,
+    pub day: Day,
+    /// Whether `src/bin/<day>.rs`, or `src/bin/<year>-<day>.rs` for a specific year, exists.
+    pub has_solution: bool,
+    /// Whether `data/puzzles/<day>.md` exists.
+    pub has_puzzle: bool,
+    /// Known correct answers of part 1 and 2.
+    pub answers: [Option<String>; 2],
+    pub timing: Option<Timing>,
+}
+
+impl DayReport {
+    /// Parts with a known correct answer count as a star.
+    pub fn stars(&self) -> usize {
+        self.answers.iter().filter(|x| x.is_some()).count()
+    }
+}
+
+/// Collects the report of every day of a year from the files in the repository.
+pub fn collect(year: Option<Year>, timings: &Timings, answers: &Answers) -> Vec<DayReport> {
+    all_days()
+        .map(|day| DayReport {
+            year,
+            day,
+            has_solution: Path::new(&get_path_for_bin(year, day)).exists(),
+            has_puzzle: Path::new(&get_puzzle_path(year, day)).exists(),
+            answers: [1, 2].map(|part| answers.get(day, part).map(Into::into)),
+            timing: timings.data.iter().find(|t| t.day == day).cloned(),
+        })
+        .collect()
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Links are relative to the report, which is written to `target/`.
+fn link(path: &str, label: &str) -> String {
+    format!(
+        r#"<a href="../{}">{label}</a>"#,
+        path.trim_start_matches("./")
+    )
+}
+
+fn format_step(step: Option<&StepTiming>) -> String {
+    step.map_or_else(|| "-".into(), |x| format!("{:.1?}", x.duration))
+}
+
+/// Renders a self-contained HTML document with one row per day.
+pub fn render(days: &[DayReport], total_millis: f64) -> String {
+    let stars: usize = days.iter().map(DayReport::stars).sum();
+    let total_stars: usize = days.iter().map(|x| usize::from(x.day.parts())).sum();
+
+    let mut html = String::from(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Advent of Code report</title>
+<style>
+body { font-family: sans-serif; margin: 2rem; color: #222; }
+table { border-collapse: collapse; }
+th, td { padding: 0.3rem 0.8rem; border-bottom: 1px solid #ddd; text-align: left; }
+td.time { font-family: monospace; text-align: right; }
+.star { color: #e6a700; }
+.missing { color: #aaa; }
+</style>
+</head>
+<body>
+<h1>Advent of Code report</h1>
+"#,
+    );
+
+    let _ = writeln!(
+        html,
+        "<p>{stars} / {total_stars} stars, total benchmark time {total_millis:.2}ms.</p>"
+    );
+    html.push_str("<table>\n<tr><th>Day</th><th>Stars</th><th>Part 1</th><th>Part 2</th><th>Parse</th><th>Time part 1</th><th>Time part 2</th><th>Links</th></tr>\n");
+
+    for report in days {
+        let stars = format!(
+            r#"<span class="star">{}</span><span class="missing">{}</span>"#,
+            "★".repeat(report.stars()),
+            "☆".repeat(usize::from(report.day.parts()).saturating_sub(report.stars()))
+        );
+
+        let [part_1, part_2] = report.answers.clone().map(|answer| {
+            answer.map_or_else(
+                || r#"<span class="missing">-</span>"#.into(),
+                |x| format!("<code>{}</code>", escape(&x)),
+            )
+        });
+
+        let timing = report.timing.as_ref();
+        let step = |f: fn(&Timing) -> Option<&StepTiming>| format_step(timing.and_then(f));
+
+        let mut links = vec![];
+        if report.has_solution {
+            links.push(link(&get_path_for_bin(report.year, report.day), "solution"));
+        }
+        if report.has_puzzle {
+            links.push(link(&get_puzzle_path(report.year, report.day), "puzzle"));
+        }
+
+        let _ = writeln!(
+            html,
+            r#"<tr><td>{}</td><td>{stars}</td><td>{part_1}</td><td>{part_2}</td><td class="time">{}</td><td class="time">{}</td><td class="time">{}</td><td>{}</td></tr>"#,
+            report.day.into_inner(),
+            step(|t| t.parse.as_ref()),
+            step(|t| t.part_1.as_ref()),
+            step(|t| t.part_2.as_ref()),
+            links.join(" ")
+        );
+    }
+
+    html.push_str("</table>\n</body>\n</html>\n");
+    html
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{render, DayReport};
+    use crate::{
+        day,
+        template::timings::{StepTiming, Timing},
+    };
+
+    fn report() -> DayReport {
+        DayReport {
+            year: None,
+            day: day!(3),
+            has_solution: true,
+            has_puzzle: false,
+            answers: [Some("<42>".into()), None],
+            timing: Some(Timing {
+                day: day!(3),
+                parse: None,
+                part_1: Some(StepTiming {
+                    duration: Duration::from_micros(15),
+                    samples: 10,
+                    stats: None,
+                }),
+                part_2: None,
+                timestamp: None,
+                commit: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn counts_stars() {
+        assert_eq!(report().stars(), 1);
+    }
+
+    #[test]
+    fn renders_days() {
+        let html = render(&[report()], 1.5);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<p>1 / 2 stars, total benchmark time 1.50ms.</p>"));
+        assert!(html.contains("<code>&lt;42&gt;</code>"));
+        assert!(html.contains(r#"<td class="time">15.0µs</td>"#));
+        assert!(html.contains(r#"<a href="../src/bin/03.rs">solution</a>"#));
+        assert!(!html.contains("puzzle</a>"));
+    }
+
+    #[test]
+    fn renders_single_part_days() {
+        let last = DayReport {
+            day: day!(25),
+            answers: [None, None],
+            timing: None,
+            ..report()
+        };
+        let html = render(&[report(), last], 0.0);
+        assert!(html.contains("<p>1 / 3 stars"));
+        assert!(html.contains(
+            r#"<tr><td>25</td><td><span class="star"></span><span class="missing">☆</span></td>"#
+        ));
+    }
+}
@@ -0,0 +1,325 @@
+/// Runs a day against every input in a directory, e.g. the inputs of several accounts.
+/// Expected answers for `<name>.txt` are read from `<name>.answers`, one line per part.
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::{
+    run_multi::{
+        self,
+        child_commands::{self, Build},
+        Output,
+    },
+    runner::{InputSource, OutputFormat, Record, RunOptions, Solution, Status, Step},
+    Day, ANSI_BOLD, ANSI_RESET,
+};
+
+/// The outcome of running a day against a single input file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InputRun {
+    pub day: Day,
+    pub name: String,
+    pub records: Vec<Record>,
+    /// Expected answers for part 1 and 2, if an answer file exists.
+    pub expected: [Option<String>; 2],
+}
+
+impl InputRun {
+    fn record(&self, step: Step) -> Option<&Record> {
+        self.records.iter().find(|r| r.step == step)
+    }
+
+    /// Whether all parts completed and match their expected answer, if one is known.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.records.iter().all(|r| r.status == Status::Completed)
+            && (1..=self.day.parts()).all(|part| match &self.expected[usize::from(part - 1)] {
+                Some(expected) => self
+                    .record(Step::Part(part))
+                    .is_some_and(|r| r.answer.as_ref() == Some(expected)),
+                None => true,
+            })
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    IO(io::Error),
+    NoInputs,
+    Run(run_multi::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::IO(e) => write!(f, "{e}"),
+            Error::NoInputs => write!(f, "directory does not contain any `.txt` inputs."),
+            Error::Run(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Runs `solution` against every `.txt` file in `dir`, in file name order.
+/// Inputs run in-process if the running binary matches `build`, in a child process otherwise.
+pub fn run_inputs(
+    solution: &Solution,
+    dir: &Path,
+    build: Build,
+    options: &RunOptions,
+) -> Result<Vec<InputRun>, Error> {
+    let inputs = find_inputs(dir)?;
+
+    if inputs.is_empty() {
+        return Err(Error::NoInputs);
+    }
+
+    let options = RunOptions {
+        format: OutputFormat::None,
+        // answers for other inputs must never be submitted.
+        submit: None,
+        year: solution.year,
+        ..options.clone()
+    };
+
+    inputs
+        .into_iter()
+        .map(|path| {
+            let records = if options.timeout.is_some() || build != Build::current() {
+                run_child(solution, &path, build, &options)?
+            } else {
+                let input = InputSource::Path(path.clone()).read(solution.year, solution.day)?;
+                (solution.run)(&input, &options)
+            };
+            Ok(InputRun {
+                day: solution.day,
+                name: path
+                    .file_name()
+                    .map_or_else(String::new, |x| x.to_string_lossy().into_owned()),
+                records,
+                expected: read_expected(&path)?,
+            })
+        })
+        .collect()
+}
+
+/// Steps that time out can not be stopped, inputs that can time out are run in a child process that exits with them.
+/// Builds other than the one of the running binary, e.g. `--dhat`, also need a child process.
+fn run_child(
+    solution: &Solution,
+    input: &Path,
+    build: Build,
+    options: &RunOptions,
+) -> Result<Vec<Record>, Error> {
+    let output = child_commands::run_solution(
+        solution.year,
+        solution.day,
+        Some(input),
+        options.time.then_some(&options.bench),
+        options.timeout,
+        build,
+        false,
+    )
+    .map_err(Error::Run)?;
+
+    Ok(output.into_iter().filter_map(Output::record).collect())
+}
+
+fn find_inputs(dir: &Path) -> Result<Vec<PathBuf>, io::Error> {
+    let mut inputs = vec![];
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|x| x == "txt") {
+            inputs.push(path);
+        }
+    }
+
+    inputs.sort();
+    Ok(inputs)
+}
+
+fn read_expected(input: &Path) -> Result<[Option<String>; 2], io::Error> {
+    match fs::read_to_string(input.with_extension("answers")) {
+        Ok(s) => Ok(parse_expected(&s)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok([None, None]),
+        Err(e) => Err(e),
+    }
+}
+
+/// Answer files hold the answer of part 1 on the first line and of part 2 on the second. Blank lines are unknown answers.
+fn parse_expected(s: &str) -> [Option<String>; 2] {
+    let mut lines = s.lines().map(|l| Some(l.trim()).filter(|l| !l.is_empty()));
+    let mut next = || lines.next().flatten().map(ToString::to_string);
+    [next(), next()]
+}
+
+fn format_cell(record: Option<&Record>, expected: Option<&str>) -> String {
+    let Some(record) = record else {
+        return "-".into();
+    };
+
+    if record.status != Status::Completed {
+        return format!("✖ {}", record.status);
+    }
+
+    let duration = format!("({:.1?})", record.duration);
+
+    let Step::Part(_) = record.step else {
+        return format!("✔ {duration}");
+    };
+
+    let answer = record
+        .answer
+        .as_deref()
+        .map_or_else(|| "✖".into(), |x| x.replace('\n', " "));
+
+    let check = match (expected, &record.answer) {
+        (None, _) => String::new(),
+        (Some(expected), Some(answer)) if expected == answer => " ✔".into(),
+        (Some(expected), _) => format!(" ✖ expected {expected}"),
+    };
+
+    format!("{answer} {duration}{check}")
+}
+
+fn construct_table(runs: &[InputRun]) -> Vec<Vec<String>> {
+    let has_parse = runs.iter().any(|run| run.record(Step::Parse).is_some());
+
+    let mut header = vec!["Input".to_string()];
+    if has_parse {
+        header.push("Parse".into());
+    }
+    let parts = runs.first().map_or(2, |run| run.day.parts());
+    header.extend((1..=parts).map(|part| format!("Part {part}")));
+
+    let mut rows = vec![header];
+
+    for run in runs {
+        let mut row = vec![run.name.clone()];
+        if has_parse {
+            row.push(format_cell(run.record(Step::Parse), None));
+        }
+        for part in 1..=parts {
+            row.push(format_cell(
+                run.record(Step::Part(part)),
+                run.expected[usize::from(part - 1)].as_deref(),
+            ));
+        }
+        rows.push(row);
+    }
+
+    rows
+}
+
+/// Prints one row per input with the answer and execution time of each part.
+pub fn print_table(runs: &[InputRun]) {
+    let rows = construct_table(runs);
+
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|i| rows.iter().map(|r| r[i].chars().count()).max().unwrap_or(0))
+        .collect();
+
+    for (i, row) in rows.iter().enumerate() {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+
+        if i == 0 {
+            println!("{ANSI_BOLD}{}{ANSI_RESET}", cells.join(" | ").trim_end());
+            let rule: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+            println!("{}", rule.join("-|-"));
+        } else {
+            println!("{}", cells.join(" | ").trim_end());
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{construct_table, parse_expected, InputRun};
+    use crate::{
+        day,
+        template::runner::{Record, Status, Step},
+    };
+
+    fn record(part: u8, answer: &str) -> Record {
+        Record {
+            step: Step::Part(part),
+            status: Status::Completed,
+            answer: Some(answer.into()),
+            duration: Duration::from_millis(1),
+            samples: 1,
+            stats: None,
+            error: None,
+        }
+    }
+
+    fn run(expected: [Option<String>; 2]) -> InputRun {
+        InputRun {
+            day: day!(1),
+            name: "alice.txt".into(),
+            records: vec![record(1, "142"), record(2, "281")],
+            expected,
+        }
+    }
+
+    #[test]
+    fn parses_expected_answers() {
+        assert_eq!(
+            parse_expected("142\n281\n"),
+            [Some("142".into()), Some("281".into())]
+        );
+        assert_eq!(parse_expected("\n281"), [None, Some("281".into())]);
+        assert_eq!(parse_expected("142"), [Some("142".into()), None]);
+    }
+
+    #[test]
+    fn checks_expected_answers() {
+        assert!(run([None, None]).is_ok());
+        assert!(run([Some("142".into()), Some("281".into())]).is_ok());
+        assert!(!run([Some("142".into()), Some("280".into())]).is_ok());
+    }
+
+    #[test]
+    fn fails_incomplete_steps() {
+        let mut run = run([None, None]);
+        run.records[1] = Record::failed(Step::Part(2), Status::Panicked, "oops".into());
+        assert!(!run.is_ok());
+    }
+
+    #[test]
+    fn constructs_table() {
+        let rows = construct_table(&[run([Some("142".into()), Some("280".into())])]);
+        assert_eq!(rows[0], ["Input", "Part 1", "Part 2"]);
+        assert_eq!(
+            rows[1],
+            ["alice.txt", "142 (1.0ms) ✔", "281 (1.0ms) ✖ expected 280"]
+        );
+    }
+
+    #[test]
+    fn constructs_table_of_single_part_days() {
+        let run = InputRun {
+            day: day!(25),
+            records: vec![record(1, "142")],
+            ..run([Some("142".into()), Some("1".into())])
+        };
+        assert!(run.is_ok());
+
+        let rows = construct_table(&[run]);
+        assert_eq!(rows[0], ["Input", "Part 1"]);
+        assert_eq!(rows[1], ["alice.txt", "142 (1.0ms) ✔"]);
+    }
+}
@@ -1,48 +1,263 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::HashSet,
+    fmt::Display,
+    io,
+    path::Path,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use rayon::prelude::*;
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    bin_name,
+    runner::{has_abandoned_jobs, print_record, BenchConfig, Record, Solution, Status},
+    Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
 use super::{
     all_days,
+    answers::{Answers, Mismatch},
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+use child_commands::Build;
+
+/// Outcome of running a set of days.
+#[derive(Debug, Default)]
+pub struct Summary {
+    /// Timings of all solved days, only present when benching.
+    pub timings: Option<Timings>,
+    /// Number of parts that were compared against a known answer.
+    pub verified: usize,
+    /// Parts whose answer did not match the known answer.
+    pub mismatches: Vec<Mismatch>,
+    /// Steps that panicked or timed out.
+    pub failures: Vec<(Day, Record)>,
+    /// Days that were scaffolded but could not be run.
+    pub errors: Vec<Day>,
+}
+
+/// Runs the solutions for a set of days. Passing a [`BenchConfig`] benches each part and returns the timings.
+///
+/// Days contained in `solutions` are run in-process, all other days are run by invoking their binary.
+/// With more than one job, days run concurrently and their records are buffered to print them in order.
+/// Output of the solutions themselves, e.g. `dbg!` or panic messages, is not buffered.
+/// Passing [`Answers`] compares every part against its known answer.
+/// Steps that take longer than `timeout` are reported as timed out, the days after them then invoke their binary.
+/// Passing a year runs the solutions of that year, see [`bin_name`].
+#[allow(clippy::too_many_arguments)]
+pub fn run_multi(
+    year: Option<Year>,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    bench: Option<&BenchConfig>,
+    timeout: Option<Duration>,
+    solutions: &[Solution],
+    jobs: usize,
+    answers: Option<&Answers>,
+) -> Summary {
+    let mut summary = Summary::default();
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
-    let mut need_space = false;
+    let mut handle_result = |day: Day, result: Result<Vec<Output>, Error>| {
+        if result.is_err() {
+            summary.errors.push(day);
+        }
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+        let records = report_day(day, result);
+
+        // days that were run are verified even without records, their known answers then count as mismatches.
+        if let Some(answers) = answers.filter(|_| is_scaffolded(year, day)) {
+            summary.verified += (1..=day.parts())
+                .filter(|&part| answers.get(day, part).is_some())
+                .count();
+            summary.mismatches.extend(answers.verify(day, &records));
+        }
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        if records.is_empty() {
+            return;
+        }
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        summary.failures.extend(
+            records
+                .iter()
+                .filter(|r| r.status != Status::Completed)
+                .map(|r| (day, r.clone())),
+        );
 
-            if output.is_empty() {
-                println!("Not solved.");
+        timings.push(child_commands::parse_exec_time(&records, day));
+    };
+
+    // NOTE: use non-duplicate, sorted day values.
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    // a step that timed out can not be stopped and keeps running in this process, which would leave the
+    // steps of all later days unbenched. these days run in a child process instead.
+    let fell_back = AtomicBool::new(false);
+    let in_process = |day: Day| {
+        let solution = solutions.iter().find(|s| s.year == year && s.day == day)?;
+        if !has_abandoned_jobs() {
+            return Some(solution);
+        }
+        if !fell_back.swap(true, Ordering::SeqCst) {
+            eprintln!("Notice: a step that timed out is still running, day {day} and later days run in a child process.");
+        }
+        None
+    };
+
+    let run_day = |day: Day, echo: bool| match in_process(day) {
+        Some(solution) => in_process::run_solution(solution, bench, timeout, echo),
+        None => {
+            let build = if is_release {
+                Build::Release
             } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+                Build::Debug
+            };
+            child_commands::run_solution(year, day, None, bench, timeout, build, echo)
+        }
+    };
+
+    if jobs > 1 {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build()
+            .expect("could not create thread pool.");
+
+        let results: Vec<_> =
+            pool.install(|| days.par_iter().map(|day| run_day(*day, false)).collect());
+
+        for (i, (day, result)) in days.iter().zip(results).enumerate() {
+            print_day_header(*day, i > 0);
+            if let Ok(output) = &result {
+                output.iter().for_each(Output::print);
             }
-        });
+            handle_result(*day, result);
+        }
+    } else {
+        for (i, day) in days.iter().enumerate() {
+            print_day_header(*day, i > 0);
+            handle_result(*day, run_day(*day, true));
+        }
+    }
 
-    if is_timed {
-        let timings = Timings { data: timings };
+    if bench.is_some() {
+        let timings = Timings {
+            data: timings,
+            history: vec![],
+        };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
+        summary.timings = Some(timings);
+    }
+
+    if !summary.failures.is_empty() {
+        print_failures(&summary.failures);
+    }
+
+    if answers.is_some() {
+        print_verification(&summary);
+    }
+
+    summary
+}
+
+fn print_failures(failures: &[(Day, Record)]) {
+    println!();
+    println!(
+        "{ANSI_BOLD}Failed:{ANSI_RESET} {} steps did not complete.",
+        failures.len()
+    );
+
+    for (day, record) in failures {
+        print!(
+            "Day {day} {}: {ANSI_BOLD}{}{ANSI_RESET}",
+            record.step, record.status
+        );
+        match &record.error {
+            Some(error) => println!(" ({error})"),
+            None => println!(),
+        }
+    }
+}
+
+fn print_verification(summary: &Summary) {
+    println!();
+
+    if summary.mismatches.is_empty() {
+        println!(
+            "{ANSI_BOLD}Verified:{ANSI_RESET} all {} known answers match.",
+            summary.verified
+        );
+        return;
+    }
+
+    println!(
+        "{ANSI_BOLD}Verification failed:{ANSI_RESET} {} of {} known answers do not match.",
+        summary.mismatches.len(),
+        summary.verified
+    );
+
+    for mismatch in &summary.mismatches {
+        println!(
+            "Day {} Part {}: expected {ANSI_BOLD}{}{ANSI_RESET}, got {ANSI_BOLD}{}{ANSI_RESET}",
+            mismatch.day,
+            mismatch.part,
+            mismatch.expected,
+            mismatch.actual.as_deref().unwrap_or("✖")
+        );
+    }
+}
+
+pub fn print_day_header(day: Day, need_space: bool) {
+    if need_space {
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+/// Reports days that could not be run and returns the records of all others.
+fn report_day(day: Day, result: Result<Vec<Output>, Error>) -> Vec<Record> {
+    match result {
+        Ok(output) => {
+            let records: Vec<Record> = output.into_iter().filter_map(Output::record).collect();
+            if records.is_empty() {
+                println!("Not solved.");
+            }
+            records
+        }
+        Err(e) => {
+            eprintln!("Failed to run day {day}: {e}");
+            vec![]
+        }
+    }
+}
+
+/// Output of a solution. Collected so that days running in parallel can be printed in order.
+#[derive(Debug)]
+pub enum Output {
+    Record(Record),
+    /// A line a solution binary printed to stdout itself.
+    Line(String),
+}
+
+impl Output {
+    pub fn print(&self) {
+        match self {
+            Output::Record(record) => print_record(record),
+            Output::Line(line) => println!("{line}"),
+        }
+    }
+
+    pub fn record(self) -> Option<Record> {
+        match self {
+            Output::Record(record) => Some(record),
+            Output::Line(_) => None,
+        }
     }
 }
 
@@ -50,6 +265,8 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
 pub enum Error {
     BrokenPipe,
     IO(io::Error),
+    MissingInput,
+    Panic,
 }
 
 impl From<std::io::Error> for Error {
@@ -58,48 +275,158 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "could not read output of solution binary."),
+            Error::IO(e) => write!(f, "{e}"),
+            Error::MissingInput => write!(f, "could not open input file."),
+            Error::Panic => write!(f, "solution panicked."),
+        }
+    }
+}
+
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(year: Option<Year>, day: Day) -> String {
+    format!("./src/bin/{}.rs", bin_name(year, day))
+}
+
+/// Whether the module file of a day exists.
+pub fn is_scaffolded(year: Option<Year>, day: Day) -> bool {
+    Path::new(&get_path_for_bin(year, day)).exists()
+}
+
+/// Solutions compiled into the main binary are called directly, without spawning a process per day.
+pub mod in_process {
+    use super::{Error, Output};
+    use crate::template::runner::{BenchConfig, InputSource, OutputFormat, RunOptions, Solution};
+    use std::{
+        panic::{self, AssertUnwindSafe},
+        time::Duration,
+    };
+
+    /// Run a registered solution against the input for its day.
+    /// Results are printed while running if `echo` is set.
+    pub fn run_solution(
+        solution: &Solution,
+        bench: Option<&BenchConfig>,
+        timeout: Option<Duration>,
+        echo: bool,
+    ) -> Result<Vec<Output>, Error> {
+        let day = solution.day;
+
+        let input = InputSource::Day
+            .read(solution.year, day)
+            .map_err(|_| Error::MissingInput)?;
+
+        let options = RunOptions {
+            format: if echo {
+                OutputFormat::Text
+            } else {
+                OutputFormat::None
+            },
+            time: bench.is_some(),
+            bench: bench.cloned().unwrap_or_default(),
+            timeout,
+            year: solution.year,
+            ..RunOptions::default()
+        };
+
+        // parts are isolated by the runner, this guards against panics outside of them.
+        let records = panic::catch_unwind(AssertUnwindSafe(|| (solution.run)(&input, &options)))
+            .map_err(|_| Error::Panic)?;
+
+        Ok(records.into_iter().map(Output::Record).collect())
+    }
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as parsing their output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{is_scaffolded, Error, Output};
+    use crate::template::{
+        bin_name,
+        runner::{BenchConfig, Record, RunOptions, Status, Step},
+        timings::StepTiming,
+        Day, Year,
+    };
     use std::{
         io::{BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
         thread,
+        time::Duration,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// How the binary of a solution is built.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Build {
+        Debug,
+        Release,
+        /// Release build with heap profiling, see `solve --dhat`.
+        Dhat,
+    }
+
+    impl Build {
+        /// The build of the running binary.
+        pub fn current() -> Self {
+            if cfg!(debug_assertions) {
+                Build::Debug
+            } else {
+                Build::Release
+            }
+        }
+
+        fn cargo_args(self) -> &'static [&'static str] {
+            match self {
+                Build::Debug => &[],
+                Build::Release => &["--release"],
+                Build::Dhat => &["--profile", "dhat", "--features", "dhat-heap"],
+            }
+        }
+    }
+
+    /// Run the solution bin for a given day, collecting the records it reports.
+    /// Passing an `input` runs the solution against that file instead of the input of the day.
+    /// Output is printed while running if `echo` is set.
+    #[allow(clippy::too_many_arguments)]
+    pub fn run_solution(
+        year: Option<Year>,
+        day: Day,
+        input: Option<&Path>,
+        bench: Option<&BenchConfig>,
+        timeout: Option<Duration>,
+        build: Build,
+        echo: bool,
+    ) -> Result<Vec<Output>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
+        if !is_scaffolded(year, day) {
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let bin = bin_name(year, day);
+        let mut args = vec!["run", "--quiet", "--bin", &bin];
+        args.extend(build.cargo_args());
+
+        args.extend(["--", "--format", "json"]);
 
-        if is_release {
-            args.push("--release");
+        let input = input.map(|x| x.display().to_string());
+        if let Some(input) = &input {
+            args.extend(["--input", input]);
         }
 
-        if is_timed {
+        if bench.is_some() {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr while grabbing the records printed to stdout.
 
         let mut cmd = Command::new("cargo")
             .args(&args)
+            .envs(bench.map(BenchConfig::to_env).into_iter().flatten())
+            .envs(timeout.map(|t| (RunOptions::TIMEOUT_ENV, t.as_millis().to_string())))
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -116,8 +443,15 @@ pub mod child_commands {
         });
 
         for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
+            let line = line?;
+            // solutions may print to stdout themselves, forward anything that is not a record.
+            let line = match Record::try_from(line.as_str()) {
+                Ok(record) => Output::Record(record),
+                Err(_) => Output::Line(line),
+            };
+            if echo {
+                line.print();
+            }
             output.push(line);
         }
 
@@ -127,67 +461,35 @@ pub mod child_commands {
         Ok(output)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
+    /// Collect the timings of the solved steps of a day.
+    pub fn parse_exec_time(records: &[Record], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
+            parse: None,
             part_1: None,
             part_2: None,
-            total_nanos: 0_f64,
+            timestamp: None,
+            commit: None,
         };
 
-        output
+        records
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+            .filter(|r| r.status == Status::Completed)
+            .filter(|r| r.step == Step::Parse || r.answer.is_some())
+            .for_each(|record| {
+                let timing = Some(StepTiming::from(record));
+
+                match record.step {
+                    Step::Parse => timings.parse = timing,
+                    Step::Part(1) => timings.part_1 = timing,
+                    Step::Part(2) => timings.part_2 = timing,
+                    Step::Part(_) => {}
                 }
-
-                timings.total_nanos += nanos;
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -205,50 +507,122 @@ pub mod child_commands {
     #[cfg(feature = "test_lib")]
     mod tests {
         use super::parse_exec_time;
+        use std::time::Duration;
+
+        use crate::{
+            day,
+            template::runner::{BenchStats, Record, Status, Step},
+        };
 
-        use crate::day;
+        fn record(step: Step, answer: Option<&str>, nanos: u64) -> Record {
+            Record {
+                step,
+                status: Status::Completed,
+                answer: answer.map(Into::into),
+                duration: Duration::from_nanos(nanos),
+                samples: 10,
+                stats: None,
+                error: None,
+            }
+        }
 
         #[test]
         fn parses_execution_times() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    record(Step::Part(1), Some("0"), 74),
+                    record(Step::Part(2), Some("10"), 74_130_000),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos(), 74130074_f64);
+            assert_eq!(res.part_1.unwrap().duration, Duration::from_nanos(74));
+            assert_eq!(
+                res.part_2.unwrap().duration,
+                Duration::from_nanos(74_130_000)
+            );
         }
 
         #[test]
         fn parses_with_patterns_in_input() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    Record::try_from(
+                        r#"{"step":"part","part":1,"status":"completed","answer":"Part 2: @ ( ) ms (1s @ 5 samples)","nanos":2000000000,"samples":5,"stats":null,"error":null}"#,
+                    )
+                    .unwrap(),
+                    record(Step::Part(2), Some("10s"), 100_000_000),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos(), 2100000000_f64);
+            assert_eq!(res.part_1.unwrap().duration, Duration::from_secs(2));
+            assert_eq!(res.part_2.unwrap().duration, Duration::from_millis(100));
+        }
+
+        #[test]
+        fn parses_bench_stats() {
+            let stats = BenchStats {
+                mean: Duration::from_micros(10),
+                min: Duration::from_micros(8),
+                median: Duration::from_nanos(9500),
+                p95: Duration::from_micros(12),
+                max: Duration::from_micros(20),
+                std_dev: Duration::from_nanos(1500),
+            };
+            let res = parse_exec_time(
+                &[Record {
+                    stats: Some(stats),
+                    ..record(Step::Part(1), Some("42"), 10_000)
+                }],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos(), 10000_f64);
+            assert_eq!(res.part_1.unwrap().duration, Duration::from_micros(10));
+            assert_eq!(res.part_1.unwrap().stats, Some(stats));
+            assert_eq!(res.part_2.is_none(), true);
+        }
+
+        #[test]
+        fn parses_parse_times() {
+            let res = parse_exec_time(
+                &[
+                    record(Step::Parse, None, 1_000_000),
+                    record(Step::Part(1), Some("42"), 2_000_000),
+                    record(Step::Part(2), Some("42"), 3_000_000),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos(), 6_000_000_f64);
+            assert_eq!(res.parse.unwrap().duration, Duration::from_millis(1));
+            assert_eq!(res.part_1.unwrap().duration, Duration::from_millis(2));
+            assert_eq!(res.part_2.unwrap().duration, Duration::from_millis(3));
+        }
+
+        #[test]
+        fn skips_failed_steps() {
+            let res = parse_exec_time(
+                &[
+                    Record::failed(Step::Parse, Status::Panicked, "oops".into()),
+                    record(Step::Part(1), Some("42"), 2_000_000),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_approx_eq!(res.total_nanos(), 2_000_000_f64);
+            assert_eq!(res.parse.is_none(), true);
+            assert_eq!(res.part_1.unwrap().duration, Duration::from_millis(2));
         }
 
         #[test]
         fn parses_missing_parts() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    record(Step::Part(1), None, 1_000),
+                    record(Step::Part(2), None, 1_000),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
+            assert_approx_eq!(res.total_nanos(), 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
@@ -1,35 +1,476 @@
 /// Encapsulates code that interacts with solution functions.
+use std::borrow::Borrow;
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{self, stdin, stdout, Read, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, process, thread};
+
+use tinyjson::JsonValue;
 
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    answers::Answers,
+    aoc_client::{self, SubmitOutcome},
+    data_dir,
+    submissions::{self, SubmitError},
+    Day, Year, ANSI_ITALIC, ANSI_RESET,
+};
+
+/// How the runner reports results.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Human-readable output, printed while the solution runs.
+    #[default]
+    Text,
+    /// One JSON [`Record`] per line, printed after each step completes.
+    Json,
+    /// Nothing is printed, the caller reports the returned records.
+    None,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            x => Err(format!(
+                "unknown output format `{x}`, expecting `text` or `json`."
+            )),
+        }
+    }
+}
+
+/// Where a solution binary reads its puzzle input from.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub enum InputSource {
+    /// The input of the day in `data/inputs`, or `data/<year>/inputs` for a specific year.
+    #[default]
+    Day,
+    Path(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Reads the whole input for `day`.
+    pub fn read(&self, year: Option<Year>, day: Day) -> io::Result<String> {
+        match self {
+            InputSource::Day => {
+                fs::read_to_string(data_dir(year).join("inputs").join(format!("{day}.txt")))
+            }
+            InputSource::Path(path) => fs::read_to_string(path),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+impl FromStr for InputSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err("expecting a path or `-` for stdin.".into()),
+            "-" => Ok(Self::Stdin),
+            path => Ok(Self::Path(PathBuf::from(path))),
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Day => write!(f, "the input of the day"),
+            InputSource::Path(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+/// Options for running a solution, parsed from the arguments passed to a solution binary.
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    pub format: OutputFormat,
+    /// Bench every part instead of executing it once.
+    pub time: bool,
+    pub bench: BenchConfig,
+    /// The part to submit, if any.
+    pub submit: Option<u8>,
+    /// Wall-clock limit for each step, including benching. `None` waits indefinitely.
+    pub timeout: Option<Duration>,
+    pub input: InputSource,
+    /// Year of the solution, set by solutions declared with `year = <year>`.
+    pub year: Option<Year>,
+}
+
+impl RunOptions {
+    pub const TIMEOUT_ENV: &'static str = "AOC_PART_TIMEOUT_MS";
+
+    /// Reads the step timeout from the environment, `None` if unset or invalid.
+    #[must_use]
+    pub fn timeout_from_env() -> Option<Duration> {
+        env::var(Self::TIMEOUT_ENV)
+            .ok()?
+            .parse()
+            .ok()
+            .map(Duration::from_millis)
+    }
+
+    /// Reads options from the command-line arguments. Exits the process on malformed input.
+    #[must_use]
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        let value_of = |flag: &str| {
+            let index = args.iter().position(|x| x == flag)?;
+            Some(args.get(index + 1).map(String::as_str).unwrap_or_default())
+        };
+
+        let format = value_of("--format").map_or(Ok(OutputFormat::Text), str::parse);
+        let format = format.unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        });
+
+        let submit = value_of("--submit").map(|x| {
+            x.parse::<u8>().unwrap_or_else(|_| {
+                eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+                process::exit(1);
+            })
+        });
+
+        let timeout = value_of("--timeout").map(|x| {
+            x.parse().map(Duration::from_millis).unwrap_or_else(|_| {
+                eprintln!("Unexpected command-line input. Format: cargo solve 1 --timeout 1000");
+                process::exit(1);
+            })
+        });
+
+        let input = value_of("--input").map_or(Ok(InputSource::Day), str::parse);
+        let input = input.unwrap_or_else(|e| {
+            eprintln!("Unexpected command-line input for --input: {e}");
+            process::exit(1);
+        });
+
+        Self {
+            format,
+            time: args.iter().any(|x| x == "--time"),
+            bench: BenchConfig::from_env(),
+            submit,
+            timeout: timeout.or_else(Self::timeout_from_env),
+            input,
+            // set by the solution, which knows its year.
+            year: None,
+        }
+    }
+}
+
+/// A step of a solution, i.e. the `parse` hook or one of the parts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Step {
+    Parse,
+    Part(u8),
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Step::Parse => write!(f, "Parse"),
+            Step::Part(part) => write!(f, "Part {part}"),
+        }
+    }
+}
+
+/// Whether a step ran to completion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Status {
+    #[default]
+    Completed,
+    Panicked,
+    TimedOut,
+}
+
+impl Status {
+    fn as_str(self) -> &'static str {
+        match self {
+            Status::Completed => "completed",
+            Status::Panicked => "panicked",
+            Status::TimedOut => "timed_out",
+        }
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::TimedOut => write!(f, "timed out"),
+            status => write!(f, "{}", status.as_str()),
+        }
+    }
+}
+
+/// The outcome of running a single step of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    pub step: Step,
+    pub status: Status,
+    /// The answer of a part, `None` if the part is not solved. Always `None` for the parse step.
+    pub answer: Option<String>,
+    /// Execution time of a single run, or the mean execution time when benched.
+    pub duration: Duration,
+    pub samples: u128,
+    pub stats: Option<BenchStats>,
+    pub error: Option<String>,
+}
+
+/// Entry point of a day's solution, generated by the [`solution!`](crate::solution) macro.
+/// The main binary collects these into a registry to run solutions in-process.
+#[derive(Clone, Copy, Debug)]
+pub struct Solution {
+    pub year: Option<Year>,
+    pub day: Day,
+    pub run: fn(&str, &RunOptions) -> Vec<Record>,
+}
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// Run a solution part on a worker thread, reporting panics and timeouts as a failed [`Status`].
+/// The worker shares ownership of the input, a part that times out keeps it alive until it finishes.
+/// Parts can take a borrowed form of the input, e.g. `&[T]` for a `Vec<T>`.
+pub fn run_part<P, I, T>(
+    func: impl Fn(&I) -> Option<T> + Send + 'static,
+    input: Arc<P>,
+    day: Day,
+    part: u8,
+    options: &RunOptions,
+) -> Record
+where
+    P: ?Sized + Borrow<I> + Send + Sync + 'static,
+    I: ?Sized,
+    T: Display + Send + 'static,
+{
+    let step = Step::Part(part);
+    let is_text = options.format == OutputFormat::Text;
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let worker_options = worker_options(step, options);
+    let outcome = run_isolated(step, options.timeout, move || {
+        run_timed(&func, (*input).borrow(), &worker_options, |result| {
+            if is_text {
+                print_result(result, &step.to_string(), "");
+            }
+        })
+    });
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let mut record = match outcome {
+        Ok((result, duration, samples, stats)) => Record {
+            step,
+            status: Status::Completed,
+            answer: result.map(|x| x.to_string()),
+            duration,
+            samples,
+            stats,
+            error: None,
+        },
+        Err((status, error)) => Record::failed(step, status, error),
+    };
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if is_text {
+        print!("\r");
+        print_record(&record);
     }
+
+    if let Some(answer) = &record.answer {
+        match submit_result(answer, day, part, options) {
+            Some(Ok(outcome)) => {
+                // keep stdout clean for machine-readable output.
+                if is_text {
+                    println!("{outcome}");
+                } else {
+                    eprintln!("{outcome}");
+                }
+
+                if outcome == SubmitOutcome::Correct {
+                    let mut answers = Answers::read_from_file(options.year);
+                    answers.set(day, part, answer);
+                    if let Err(e) = answers.store_file(options.year) {
+                        record.error = Some(format!("failed to store answer: {e}"));
+                    }
+                }
+            }
+            Some(Err(e)) => {
+                record.error = Some(format!("failed to submit result: {e}"));
+            }
+            None => {}
+        }
+    }
+
+    if options.format == OutputFormat::Json {
+        println!("{}", record.to_json());
+    }
+
+    record
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+/// Run the `parse` hook of a solution and return its output, timing it like a solution part.
+/// The output is `None` if the hook panicked or timed out.
+pub fn run_parse<P>(
+    func: impl Fn(&str) -> P + Send + 'static,
+    input: Arc<str>,
+    options: &RunOptions,
+) -> (Option<P>, Record)
+where
+    P: Send + 'static,
+{
+    let is_text = options.format == OutputFormat::Text;
+
+    let worker_options = worker_options(Step::Parse, options);
+    let outcome = run_isolated(Step::Parse, options.timeout, move || {
+        run_timed(&func, &*input, &worker_options, |_| {
+            if is_text {
+                print!("Parse: ✔");
+            }
+        })
+    });
+
+    let (parsed, record) = match outcome {
+        Ok((parsed, duration, samples, stats)) => (
+            Some(parsed),
+            Record {
+                step: Step::Parse,
+                status: Status::Completed,
+                answer: None,
+                duration,
+                samples,
+                stats,
+                error: None,
+            },
+        ),
+        Err((status, error)) => (None, Record::failed(Step::Parse, status, error)),
+    };
+
+    match options.format {
+        OutputFormat::Text => {
+            print!("\r");
+            print_record(&record);
+        }
+        OutputFormat::Json => println!("{}", record.to_json()),
+        OutputFormat::None => {}
+    }
+
+    (parsed, record)
+}
+
+/// Matches the default stack size of the main thread on most platforms, solutions are written against that.
+const WORKER_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Number of jobs that timed out and are still running in the background.
+static ABANDONED_JOBS: AtomicUsize = AtomicUsize::new(0);
+
+const JOB_RUNNING: u8 = 0;
+const JOB_DONE: u8 = 1;
+const JOB_ABANDONED: u8 = 2;
+
+/// Whether a step that timed out is still running in the background.
+pub fn has_abandoned_jobs() -> bool {
+    ABANDONED_JOBS.load(Ordering::SeqCst) > 0
+}
+
+/// Steps that timed out keep running and compete for the CPU, later steps are not benched then.
+fn worker_options(step: Step, options: &RunOptions) -> RunOptions {
+    let mut options = options.clone();
+
+    if options.time && has_abandoned_jobs() {
+        eprintln!(
+            "Warning: a step that timed out is still running, {step} is not benched and its timing is unreliable."
+        );
+        options.time = false;
+    }
+
+    options
+}
+
+/// Run `job` on its own thread, catching panics and waiting at most `timeout` for it to finish.
+/// A job that times out is abandoned and keeps running in the background until it finishes or the process exits.
+fn run_isolated<T: Send + 'static>(
+    step: Step,
+    timeout: Option<Duration>,
+    job: impl FnOnce() -> T + Send + 'static,
+) -> Result<T, (Status, String)> {
+    let (sender, receiver) = mpsc::channel();
+    let state = Arc::new(AtomicU8::new(JOB_RUNNING));
+    let worker_state = Arc::clone(&state);
+
+    let spawned = thread::Builder::new()
+        .name(step.to_string())
+        .stack_size(WORKER_STACK_SIZE)
+        .spawn(move || {
+            // the receiver is gone if the job timed out, the result is not needed anymore then.
+            let _ = sender.send(panic::catch_unwind(AssertUnwindSafe(job)));
+            if worker_state.swap(JOB_DONE, Ordering::SeqCst) == JOB_ABANDONED {
+                ABANDONED_JOBS.fetch_sub(1, Ordering::SeqCst);
+            }
+        });
+
+    if let Err(e) = spawned {
+        return Err((Status::Panicked, format!("failed to spawn thread: {e}")));
+    }
+
+    let received = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout).map_err(|e| match e {
+            mpsc::RecvTimeoutError::Timeout => {
+                // a job that finished in the meantime is not counted as running.
+                if state
+                    .compare_exchange(
+                        JOB_RUNNING,
+                        JOB_ABANDONED,
+                        Ordering::SeqCst,
+                        Ordering::SeqCst,
+                    )
+                    .is_ok()
+                {
+                    ABANDONED_JOBS.fetch_add(1, Ordering::SeqCst);
+                }
+                (
+                    Status::TimedOut,
+                    format!("did not finish within {timeout:.1?}."),
+                )
+            }
+            mpsc::RecvTimeoutError::Disconnected => (Status::Panicked, e.to_string()),
+        }),
+        None => receiver
+            .recv()
+            .map_err(|e| (Status::Panicked, e.to_string())),
+    }?;
+
+    received.map_err(|payload| (Status::Panicked, panic_message(payload.as_ref())))
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "panicked with a non-string payload.".into())
+}
+
+/// Run a solution part. The behavior differs depending on whether timing is enabled:
+///  1. if it is not, the function is executed once.
+///  2. if it is, the function is benched according to the configured [`BenchConfig`].
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    options: &RunOptions,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, u128, Option<BenchStats>) {
     let timer = Instant::now();
     let result = {
         let input = input.clone();
@@ -43,27 +484,42 @@ fn run_timed<I: Clone, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
-    };
+    if options.time {
+        if options.format == OutputFormat::Text {
+            print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+            let _ = stdout().flush();
+        }
 
-    (result, run.0, run.1)
+        let (stats, samples) = bench(func, input, &base_time, &options.bench);
+        (result, stats.mean, samples, Some(stats))
+    } else {
+        (result, base_time, 1, None)
+    }
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    config: &BenchConfig,
+) -> (BenchStats, u128) {
+    // results of the warm-up phase are discarded, it only serves to estimate the time per iteration.
+    let mut warmup_runs: u32 = 0;
+    let warmup_timer = Instant::now();
+    while warmup_timer.elapsed() < config.warmup {
+        black_box(func(black_box(input.clone())));
+        warmup_runs += 1;
+    }
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    let estimate = if warmup_runs == 0 {
+        *base_time
+    } else {
+        warmup_timer.elapsed() / warmup_runs
+    };
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
+    let bench_iterations = (config.budget.as_nanos() / cmp::max(estimate.as_nanos(), 10)).clamp(
+        config.min_samples,
+        cmp::max(config.min_samples, config.max_samples),
     );
 
     let mut timers: Vec<Duration> = vec![];
@@ -76,11 +532,105 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
+    (BenchStats::from_samples(&timers), bench_iterations)
+}
+
+/// Controls how long and how often a solution part is executed when benching.
+///
+/// Solution binaries read this from the environment, the `solve` and `time` commands forward their flags this way.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Time spent executing the part before samples are collected.
+    pub warmup: Duration,
+    /// Approximate time spent collecting samples.
+    pub budget: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+}
+
+impl BenchConfig {
+    pub const WARMUP_ENV: &'static str = "AOC_BENCH_WARMUP_MS";
+    pub const BUDGET_ENV: &'static str = "AOC_BENCH_BUDGET_MS";
+    pub const MIN_SAMPLES_ENV: &'static str = "AOC_BENCH_MIN_SAMPLES";
+    pub const MAX_SAMPLES_ENV: &'static str = "AOC_BENCH_MAX_SAMPLES";
+
+    /// Reads the config from environment variables, using defaults for unset or invalid values.
+    #[must_use]
+    pub fn from_env() -> Self {
+        fn read_var<T: std::str::FromStr>(key: &str) -> Option<T> {
+            env::var(key).ok()?.parse().ok()
+        }
+
+        let default = Self::default();
+
+        Self {
+            warmup: read_var(Self::WARMUP_ENV).map_or(default.warmup, Duration::from_millis),
+            budget: read_var(Self::BUDGET_ENV).map_or(default.budget, Duration::from_millis),
+            min_samples: read_var(Self::MIN_SAMPLES_ENV).unwrap_or(default.min_samples),
+            max_samples: read_var(Self::MAX_SAMPLES_ENV).unwrap_or(default.max_samples),
+        }
+    }
+
+    /// Environment variables that make a solution binary pick up this config.
+    #[must_use]
+    pub fn to_env(&self) -> [(&'static str, String); 4] {
+        [
+            (Self::WARMUP_ENV, self.warmup.as_millis().to_string()),
+            (Self::BUDGET_ENV, self.budget.as_millis().to_string()),
+            (Self::MIN_SAMPLES_ENV, self.min_samples.to_string()),
+            (Self::MAX_SAMPLES_ENV, self.max_samples.to_string()),
+        ]
+    }
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup: Duration::from_millis(100),
+            budget: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10000,
+        }
+    }
+}
+
+/// Summary statistics of the samples collected while benching a solution part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchStats {
+    pub mean: Duration,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+    pub std_dev: Duration,
+}
+
+impl BenchStats {
+    /// Computes statistics for a non-empty set of samples.
+    #[must_use]
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let mean = average_duration(&sorted);
+
+        #[allow(clippy::cast_precision_loss)]
+        let variance = sorted
+            .iter()
+            .map(|x| (x.as_nanos() as f64 - mean as f64).powi(2))
+            .sum::<f64>()
+            / sorted.len() as f64;
+
         #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+        Self {
+            mean: Duration::from_nanos(mean as u64),
+            min: sorted[0],
+            median: median_duration(&sorted),
+            p95: percentile_duration(&sorted, 95),
+            max: sorted[sorted.len() - 1],
+            std_dev: Duration::from_secs_f64(variance.sqrt() / 1_000_000_000_f64),
+        }
+    }
 }
 
 fn average_duration(numbers: &[Duration]) -> u128 {
@@ -91,9 +641,30 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
+/// Expects `sorted` to be sorted in ascending order.
+fn median_duration(sorted: &[Duration]) -> Duration {
+    let mid = sorted.len() / 2;
+    if sorted.len() % 2 == 1 {
+        sorted[mid]
+    } else {
+        (sorted[mid - 1] + sorted[mid]) / 2
+    }
+}
+
+/// Nearest-rank percentile. Expects `sorted` to be sorted in ascending order.
+fn percentile_duration(sorted: &[Duration], percentile: usize) -> Duration {
+    let rank = (percentile * sorted.len()).div_ceil(100);
+    sorted[rank.saturating_sub(1)]
+}
+
+fn format_duration(duration: &Duration, samples: u128, stats: Option<&BenchStats>) -> String {
     if samples == 1 {
         format!(" ({duration:.1?})")
+    } else if let Some(stats) = stats {
+        format!(
+            " ({duration:.1?} @ {samples} samples; min {:.1?}, median {:.1?}, p95 {:.1?}, max {:.1?}, σ {:.1?})",
+            stats.min, stats.median, stats.p95, stats.max, stats.std_dev
+        )
     } else {
         format!(" ({duration:.1?} @ {samples} samples)")
     }
@@ -109,7 +680,6 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
-                    print!("\r");
                     println!("{str}");
                     println!("{result}");
                 }
@@ -118,7 +688,6 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
-                    print!("\r");
                     println!("{str}");
                 }
             }
@@ -127,48 +696,362 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
-                print!("\r");
                 println!("{part}: ✖             ");
             }
         }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
+/// Print the human-readable representation of a record.
+pub fn print_record(record: &Record) {
+    let duration_str = format_duration(&record.duration, record.samples, record.stats.as_ref());
+
+    match (record.status, record.step) {
+        (Status::Completed, Step::Parse) => println!("Parse: ✔{duration_str}"),
+        (Status::Completed, Step::Part(_)) => {
+            print_result(&record.answer, &record.step.to_string(), &duration_str);
+        }
+        (status, step) => println!("{step}: ✖ {ANSI_BOLD}{status}{ANSI_RESET}             "),
+    }
+
+    if let Some(error) = &record.error {
+        eprintln!("{}: {error}", record.step);
+    }
+}
+
+/// Try to submit one part of the solution if:
+///  1. the part was requested via `--submit <part>`.
+///  2. a session token is configured.
+///  3. the submission log does not rule the answer out.
+fn submit_result(
+    result: &str,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
-
-    if !args.contains(&"--submit".into()) {
+    options: &RunOptions,
+) -> Option<Result<SubmitOutcome, SubmitError>> {
+    if options.submit != Some(part) {
         return None;
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    let client = match aoc_client::Client::from_env(options.year) {
+        Ok(client) => client,
+        Err(e) => return Some(Err(SubmitError::Client(e))),
+    };
+
+    if options.input != InputSource::Day {
+        eprintln!(
+            "Warning: submitting a result computed from {}, make sure it is the input of your account.",
+            options.input
+        );
+    }
+
+    // keep stdout clean for machine-readable output.
+    if options.format == OutputFormat::Text {
+        println!("Submitting result...");
+    } else {
+        eprintln!("Submitting result...");
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    Some(submissions::submit(
+        &client,
+        options.year,
+        day,
+        part,
+        result,
+    ))
+}
+
+/* -------------------------------------------------------------------------- */
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+impl Record {
+    /// A step that did not run to completion.
+    #[must_use]
+    pub fn failed(step: Step, status: Status, error: String) -> Self {
+        Record {
+            step,
+            status,
+            answer: None,
+            duration: Duration::ZERO,
+            samples: 0,
+            stats: None,
+            error: Some(error),
+        }
+    }
+
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        JsonValue::from(self)
+            .stringify()
+            .expect("records only contain finite numbers.")
+    }
+}
+
+impl From<&Record> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Record) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let (step, part) = match value.step {
+            Step::Parse => ("parse", JsonValue::Null),
+            Step::Part(part) => ("part", JsonValue::Number(f64::from(part))),
+        };
+
+        map.insert("step".into(), JsonValue::String(step.into()));
+        map.insert("part".into(), part);
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+        map.insert(
+            "answer".into(),
+            value
+                .answer
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "nanos".into(),
+            JsonValue::Number(value.duration.as_nanos() as f64),
+        );
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("stats".into(), stats_to_json(value.stats));
+        map.insert(
+            "error".into(),
+            value
+                .error
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Record {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        let optional_string = |key: &str| match json.get(key) {
+            Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+            _ => Err(format!("Expected record.{key} to be null or string.")),
+        };
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected record.{key} to be a number."))
+        };
+
+        let step = match json
+            .get("step")
+            .and_then(|v| v.get::<String>())
+            .map(String::as_str)
+        {
+            Some("parse") => Step::Parse,
+            Some("part") => Step::Part(number("part")? as u8),
+            _ => return Err("Expected record.step to be `parse` or `part`.".into()),
+        };
+
+        let status = match json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .map(String::as_str)
+        {
+            Some("completed") => Status::Completed,
+            Some("panicked") => Status::Panicked,
+            Some("timed_out") => Status::TimedOut,
+            _ => {
+                return Err(
+                    "Expected record.status to be `completed`, `panicked` or `timed_out`.".into(),
+                )
+            }
+        };
+
+        let stats = json
+            .get("stats")
+            .map_or(Ok(None), stats_from_json)
+            .map_err(|()| "Expected record.stats to be null or a stats object.")?;
+
+        Ok(Record {
+            step,
+            status,
+            answer: optional_string("answer")?,
+            duration: Duration::from_nanos(number("nanos")? as u64),
+            samples: number("samples")? as u128,
+            stats,
+            error: optional_string("error")?,
+        })
+    }
+}
+
+impl TryFrom<&str> for Record {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+        Record::try_from(&json)
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn stats_to_json(stats: Option<BenchStats>) -> JsonValue {
+    let Some(stats) = stats else {
+        return JsonValue::Null;
     };
 
-    if part_submit != part {
-        return None;
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+    for (key, duration) in [
+        ("mean", stats.mean),
+        ("min", stats.min),
+        ("median", stats.median),
+        ("p95", stats.p95),
+        ("max", stats.max),
+        ("std_dev", stats.std_dev),
+    ] {
+        map.insert(key.into(), JsonValue::Number(duration.as_nanos() as f64));
+    }
+
+    JsonValue::Object(map)
+}
+
+pub(crate) fn stats_from_json(value: &JsonValue) -> Result<Option<BenchStats>, ()> {
+    if value.is_null() {
+        return Ok(None);
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    let json = value.get::<HashMap<String, JsonValue>>().ok_or(())?;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let get = |key: &str| {
+        json.get(key)
+            .and_then(|v| v.get::<f64>())
+            .map(|nanos| Duration::from_nanos(*nanos as u64))
+            .ok_or(())
+    };
+
+    Ok(Some(BenchStats {
+        mean: get("mean")?,
+        min: get("min")?,
+        median: get("median")?,
+        p95: get("p95")?,
+        max: get("max")?,
+        std_dev: get("std_dev")?,
+    }))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{run_isolated, worker_options, BenchStats, Record, RunOptions, Status, Step};
+    use std::time::Duration;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_millis(*x)).collect()
+    }
+
+    #[test]
+    fn computes_stats_for_odd_samples() {
+        let stats = BenchStats::from_samples(&millis(&[5, 1, 3, 2, 4]));
+        assert_eq!(stats.mean, Duration::from_millis(3));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_millis(5));
+        assert_eq!(stats.max, Duration::from_millis(5));
+        assert_eq!(stats.std_dev.as_micros(), 1414);
+    }
+
+    #[test]
+    fn computes_stats_for_even_samples() {
+        let stats = BenchStats::from_samples(&millis(&[4, 1, 3, 2]));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.p95, Duration::from_millis(4));
+    }
+
+    #[test]
+    fn computes_p95_for_many_samples() {
+        let samples: Vec<u64> = (1..=100).collect();
+        let stats = BenchStats::from_samples(&millis(&samples));
+        assert_eq!(stats.p95, Duration::from_millis(95));
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = BenchStats::from_samples(&millis(&[7]));
+        assert_eq!(stats.min, stats.max);
+        assert_eq!(stats.median, Duration::from_millis(7));
+        assert_eq!(stats.std_dev, Duration::ZERO);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn serializes_records() {
+        let record = Record {
+            step: Step::Part(2),
+            status: Status::Completed,
+            answer: Some("multi\nline \"answer\"".into()),
+            duration: Duration::from_micros(12),
+            samples: 100,
+            stats: Some(BenchStats::from_samples(&millis(&[1, 2, 3]))),
+            error: None,
+        };
+        assert_eq!(Record::try_from(record.to_json().as_str()), Ok(record));
+    }
+
+    #[test]
+    fn serializes_parse_records() {
+        let record = Record {
+            step: Step::Parse,
+            status: Status::Completed,
+            answer: None,
+            duration: Duration::from_micros(12),
+            samples: 1,
+            stats: None,
+            error: Some("oops".into()),
+        };
+        assert_eq!(Record::try_from(record.to_json().as_str()), Ok(record));
+    }
+
+    #[test]
+    fn serializes_failed_records() {
+        let record = Record::failed(Step::Part(1), Status::TimedOut, "too slow".into());
+        assert_eq!(Record::try_from(record.to_json().as_str()), Ok(record));
+    }
+
+    #[test]
+    fn isolates_panics() {
+        let result = run_isolated(Step::Part(1), None, || -> u32 { panic!("oops") });
+        assert_eq!(result, Err((Status::Panicked, "oops".into())));
+    }
+
+    #[test]
+    fn times_out_long_running_steps() {
+        let result = run_isolated(Step::Part(1), Some(Duration::from_millis(10)), || {
+            std::thread::sleep(Duration::from_secs(1));
+        });
+        assert!(matches!(result, Err((Status::TimedOut, _))));
+
+        // the step is still running, later steps are not benched.
+        let options = RunOptions {
+            time: true,
+            ..RunOptions::default()
+        };
+        assert!(!worker_options(Step::Part(2), &options).time);
+    }
+
+    #[test]
+    fn returns_results_of_completed_steps() {
+        let result = run_isolated(Step::Parse, Some(Duration::from_secs(10)), || 42);
+        assert_eq!(result, Ok(42));
+    }
+
+    #[test]
+    fn rejects_non_record_lines() {
+        assert!(Record::try_from("Part 1: 42 (1.0ms @ 10 samples)").is_err());
+        assert!(Record::try_from(r#"{"step":"part"}"#).is_err());
+    }
 }
@@ -0,0 +1,517 @@
+/// Module that keeps a log of submitted answers and refuses submissions that are known to fail.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs, io,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    aoc_client::{self, Hint, SubmitOutcome},
+    data_dir, Day, Year,
+};
+
+static SUBMISSIONS_FILE_NAME: &str = "submissions.json";
+
+/// The verdict of the website on a submission.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    /// Wrong, without a hint whether the answer was too high or too low.
+    Incorrect,
+    TooHigh,
+    TooLow,
+    WrongLevel,
+    /// The answer was not judged because it was submitted too early.
+    RateLimited,
+}
+
+impl Verdict {
+    fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::WrongLevel => "wrong_level",
+            Verdict::RateLimited => "rate_limited",
+        }
+    }
+
+    fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            Verdict::Incorrect | Verdict::TooHigh | Verdict::TooLow
+        )
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            Verdict::Correct,
+            Verdict::Incorrect,
+            Verdict::TooHigh,
+            Verdict::TooLow,
+            Verdict::WrongLevel,
+            Verdict::RateLimited,
+        ]
+        .into_iter()
+        .find(|x| x.as_str() == s)
+        .ok_or(format!("unknown verdict `{s}`."))
+    }
+}
+
+impl From<&SubmitOutcome> for Verdict {
+    fn from(value: &SubmitOutcome) -> Self {
+        match value {
+            SubmitOutcome::Correct => Verdict::Correct,
+            SubmitOutcome::Incorrect { hint, .. } => match hint {
+                Some(Hint::TooHigh) => Verdict::TooHigh,
+                Some(Hint::TooLow) => Verdict::TooLow,
+                None => Verdict::Incorrect,
+            },
+            SubmitOutcome::WrongLevel => Verdict::WrongLevel,
+        }
+    }
+}
+
+/// A single submitted answer. Timestamps are seconds since the unix epoch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub timestamp: u64,
+    pub verdict: Verdict,
+    /// When the website accepts the next submission, if it reported a wait time.
+    pub wait_until: Option<u64>,
+}
+
+/// The log of all submitted answers.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+/// Reason to not send an answer to the website.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    /// The same answer was rejected before.
+    AlreadyRejected(Verdict),
+    /// The part was already solved with the contained answer.
+    AlreadySolved(String),
+    /// The website asked to wait before submitting again. Holds the remaining wait time.
+    Cooldown(Duration),
+    /// A previous answer that was too high or too low rules out this answer.
+    OutOfBounds { hint: Hint, bound: String },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadyRejected(verdict) => write!(
+                f,
+                "this answer was already submitted and rejected ({}).",
+                verdict.as_str().replace('_', " ")
+            ),
+            Refusal::AlreadySolved(answer) => {
+                write!(f, "this part was already solved with `{answer}`.")
+            }
+            Refusal::Cooldown(wait) => {
+                write!(f, "the website asked to wait, {wait:?} left to wait.")
+            }
+            Refusal::OutOfBounds {
+                hint: Hint::TooHigh,
+                bound,
+            } => write!(f, "`{bound}` was already too high."),
+            Refusal::OutOfBounds {
+                hint: Hint::TooLow,
+                bound,
+            } => write!(f, "`{bound}` was already too low."),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Refused(Refusal),
+    Client(aoc_client::Error),
+}
+
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitError::Refused(refusal) => write!(f, "refusing to submit, {refusal}"),
+            SubmitError::Client(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Submissions {
+    /// Dehydrate submissions to the JSON file of a year, `data/submissions.json` if none is passed.
+    pub fn store_file(&self, year: Option<Year>) -> Result<(), io::Error> {
+        let path = data_dir(year).join(SUBMISSIONS_FILE_NAME);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions from the JSON file of a year. If not present, returns an empty log.
+    pub fn read_from_file(year: Option<Year>) -> Self {
+        let s = match fs::read_to_string(data_dir(year).join(SUBMISSIONS_FILE_NAME)) {
+            Ok(s) => Submissions::try_from(s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Submissions::default()),
+            Err(e) => Err(e.to_string()),
+        };
+
+        match s {
+            Ok(submissions) => submissions,
+            Err(e) => {
+                eprintln!("{e}");
+                Submissions::default()
+            }
+        }
+    }
+
+    /// Appends a submission to the log.
+    pub fn record(&mut self, submission: Submission) {
+        self.data.push(submission);
+    }
+
+    /// Checks whether `answer` can be submitted at `now`, based on earlier submissions.
+    pub fn check(&self, day: Day, part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+        let answer = answer.trim();
+        let previous = || {
+            self.data
+                .iter()
+                .filter(move |x| x.day == day && x.part == part)
+        };
+
+        if let Some(solved) = previous().find(|x| x.verdict == Verdict::Correct) {
+            return Err(Refusal::AlreadySolved(solved.answer.clone()));
+        }
+
+        if let Some(rejected) = previous().find(|x| x.verdict.is_wrong() && x.answer == answer) {
+            return Err(Refusal::AlreadyRejected(rejected.verdict));
+        }
+
+        // the cooldown applies to the whole account, not just this part.
+        if let Some(wait_until) = self.data.iter().filter_map(|x| x.wait_until).max() {
+            if wait_until > now {
+                return Err(Refusal::Cooldown(Duration::from_secs(wait_until - now)));
+            }
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bound = |verdict: Verdict| {
+            previous()
+                .filter(move |x| x.verdict == verdict)
+                .filter_map(|x| Some((x.answer.parse::<i128>().ok()?, &x.answer)))
+        };
+
+        if let Some((_, bound)) = bound(Verdict::TooHigh)
+            .filter(|(high, _)| value >= *high)
+            .min_by_key(|(high, _)| *high)
+        {
+            return Err(Refusal::OutOfBounds {
+                hint: Hint::TooHigh,
+                bound: bound.clone(),
+            });
+        }
+
+        if let Some((_, bound)) = bound(Verdict::TooLow)
+            .filter(|(low, _)| value <= *low)
+            .max_by_key(|(low, _)| *low)
+        {
+            return Err(Refusal::OutOfBounds {
+                hint: Hint::TooLow,
+                bound: bound.clone(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|x| x.as_secs())
+        .unwrap_or_default()
+}
+
+/// Submits an answer unless the submission log rules it out, then records the verdict of the website.
+pub fn submit(
+    client: &aoc_client::Client,
+    year: Option<Year>,
+    day: Day,
+    part: u8,
+    answer: &str,
+) -> Result<SubmitOutcome, SubmitError> {
+    let mut submissions = Submissions::read_from_file(year);
+    let timestamp = now();
+
+    submissions
+        .check(day, part, answer, timestamp)
+        .map_err(SubmitError::Refused)?;
+
+    let result = client.submit(day, part, answer);
+
+    let (verdict, wait) = match &result {
+        Ok(outcome @ SubmitOutcome::Incorrect { wait, .. }) => (Verdict::from(outcome), *wait),
+        Ok(outcome) => (Verdict::from(outcome), None),
+        Err(aoc_client::Error::RateLimited(wait)) => (Verdict::RateLimited, *wait),
+        // nothing was judged, e.g. the website could not be reached.
+        Err(_) => return result.map_err(SubmitError::Client),
+    };
+
+    submissions.record(Submission {
+        day,
+        part,
+        answer: answer.trim().into(),
+        timestamp,
+        verdict,
+        wait_until: wait.map(|x| timestamp + x.as_secs()),
+    });
+    // the answer was judged already, so a log that can not be written must not hide the verdict.
+    if let Err(e) = submissions.store_file(year) {
+        eprintln!("Warning: could not store submission log: {e}");
+    }
+
+    result.map_err(SubmitError::Client)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.as_str().into()),
+        );
+        map.insert(
+            "wait_until".into(),
+            value
+                .wait_until
+                .map_or(JsonValue::Null, |x| JsonValue::Number(x as f64)),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u8)
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .cloned()
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64)
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?
+            .parse()?;
+
+        let wait_until = match json.get("wait_until") {
+            None | Some(JsonValue::Null) => None,
+            Some(JsonValue::Number(x)) => Some(*x as u64),
+            _ => return Err("Expected submission.wait_until to be null or a number.".into()),
+        };
+
+        Ok(Submission {
+            day,
+            part,
+            answer,
+            timestamp,
+            verdict,
+            wait_until,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Refusal, Submission, Submissions, Verdict};
+    use crate::{day, template::aoc_client::Hint};
+
+    fn submission(part: u8, answer: &str, verdict: Verdict) -> Submission {
+        Submission {
+            day: day!(1),
+            part,
+            answer: answer.into(),
+            timestamp: 1_000,
+            verdict,
+            wait_until: None,
+        }
+    }
+
+    #[test]
+    fn refuses_rejected_answers() {
+        let submissions = Submissions {
+            data: vec![submission(1, "abc", Verdict::Incorrect)],
+        };
+        assert_eq!(
+            submissions.check(day!(1), 1, "abc", 2_000),
+            Err(Refusal::AlreadyRejected(Verdict::Incorrect))
+        );
+        assert_eq!(submissions.check(day!(1), 1, "abd", 2_000), Ok(()));
+        assert_eq!(submissions.check(day!(1), 2, "abc", 2_000), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let submissions = Submissions {
+            data: vec![submission(2, "42", Verdict::Correct)],
+        };
+        assert_eq!(
+            submissions.check(day!(1), 2, "43", 2_000),
+            Err(Refusal::AlreadySolved("42".into()))
+        );
+    }
+
+    #[test]
+    fn enforces_cooldown() {
+        let mut limited = submission(2, "7", Verdict::RateLimited);
+        limited.wait_until = Some(1_060);
+        let submissions = Submissions {
+            data: vec![limited],
+        };
+        assert_eq!(
+            submissions.check(day!(1), 1, "8", 1_020),
+            Err(Refusal::Cooldown(Duration::from_secs(40)))
+        );
+        assert_eq!(submissions.check(day!(1), 1, "8", 1_060), Ok(()));
+    }
+
+    #[test]
+    fn enforces_bounds() {
+        let submissions = Submissions {
+            data: vec![
+                submission(1, "100", Verdict::TooHigh),
+                submission(1, "80", Verdict::TooHigh),
+                submission(1, "-5", Verdict::TooLow),
+            ],
+        };
+        assert_eq!(
+            submissions.check(day!(1), 1, "90", 2_000),
+            Err(Refusal::OutOfBounds {
+                hint: Hint::TooHigh,
+                bound: "80".into()
+            })
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "-10", 2_000),
+            Err(Refusal::OutOfBounds {
+                hint: Hint::TooLow,
+                bound: "-5".into()
+            })
+        );
+        assert_eq!(submissions.check(day!(1), 1, "79", 2_000), Ok(()));
+        assert_eq!(submissions.check(day!(1), 1, "abc", 2_000), Ok(()));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let mut limited = submission(1, "7", Verdict::RateLimited);
+        limited.wait_until = Some(1_060);
+        let submissions = Submissions {
+            data: vec![submission(1, "100", Verdict::TooHigh), limited],
+        };
+
+        let json = tinyjson::JsonValue::from(submissions.clone())
+            .stringify()
+            .unwrap();
+        assert_eq!(Submissions::try_from(json), Ok(submissions));
+    }
+}
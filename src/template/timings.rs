@@ -1,37 +1,137 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, path::PathBuf, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{
+    data_dir,
+    runner::{stats_from_json, stats_to_json, BenchStats, Record, Step},
+    Day, Year,
+};
+
+static TIMINGS_FILE_NAME: &str = "timings.json";
+static PROFILES_DIR_NAME: &str = "timings";
+
+/// Name of the profile stored in `data/timings.json` when passed to commands that take a profile.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Version of the JSON format written by [`Timings::store_file`].
+/// Files without a version are version 1, which stored formatted strings like `"74.1ms"` per part.
+const SCHEMA_VERSION: u8 = 2;
+
+/// Represents the benchmark result of a single step of a day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StepTiming {
+    /// Execution time of a single run, or the mean execution time when benched.
+    pub duration: Duration,
+    /// Number of samples, `0` for timings migrated from a file that did not record it.
+    pub samples: u128,
+    pub stats: Option<BenchStats>,
+}
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+impl From<&Record> for StepTiming {
+    fn from(record: &Record) -> Self {
+        StepTiming {
+            duration: record.duration,
+            samples: record.samples,
+            stats: record.stats,
+        }
+    }
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
-    pub total_nanos: f64,
+    pub parse: Option<StepTiming>,
+    pub part_1: Option<StepTiming>,
+    pub part_2: Option<StepTiming>,
+    /// Seconds since the unix epoch at which the timing was stored.
+    pub timestamp: Option<u64>,
+    /// The git commit the timing was measured at, if available.
+    pub commit: Option<String>,
+}
+
+impl Timing {
+    /// Timing of a step, if it was benched.
+    pub fn step(&self, step: Step) -> Option<&StepTiming> {
+        match step {
+            Step::Parse => self.parse.as_ref(),
+            Step::Part(1) => self.part_1.as_ref(),
+            Step::Part(2) => self.part_2.as_ref(),
+            Step::Part(_) => None,
+        }
+    }
+
+    /// Whether every part of the day was benched, see [`Day::parts`].
+    pub fn is_complete(&self) -> bool {
+        (1..=self.day.parts()).all(|part| self.step(Step::Part(part)).is_some())
+    }
+
+    /// Sum of the execution times of all steps as nanos.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn total_nanos(&self) -> f64 {
+        [&self.parse, &self.part_1, &self.part_2]
+            .into_iter()
+            .flatten()
+            .map(|x| x.duration.as_nanos() as f64)
+            .sum()
+    }
 }
 
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
+    /// The latest timing of each day.
     pub data: Vec<Timing>,
+    /// Timings that were superseded by a later timing of the same day, oldest first.
+    pub history: Vec<Timing>,
+}
+
+/// A step that got slower than its previously stored timing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Regression {
+    pub day: Day,
+    pub step: Step,
+    pub previous: Duration,
+    pub current: Duration,
+}
+
+impl Regression {
+    /// Relative change of the mean execution time in percent.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn percent(&self) -> f64 {
+        (self.current.as_nanos() as f64 / self.previous.as_nanos() as f64 - 1_f64) * 100_f64
+    }
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Path of the JSON file of a profile, e.g. of one machine.
+    /// Named profiles live in `data/timings/<profile>.json`, the default profile in `data/timings.json`.
+    /// Timings of a specific year live in `data/<year>` instead.
+    pub fn file_path(year: Option<Year>, profile: Option<&str>) -> PathBuf {
+        match profile {
+            Some(name) if name != DEFAULT_PROFILE => data_dir(year)
+                .join(PROFILES_DIR_NAME)
+                .join(format!("{name}.json")),
+            _ => data_dir(year).join(TIMINGS_FILE_NAME),
+        }
+    }
+
+    /// Dehydrate timings to the JSON file of a profile.
+    pub fn store_file(&self, year: Option<Year>, profile: Option<&str>) -> Result<(), Error> {
+        let path = Self::file_path(year, profile);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(path)?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
+    /// Rehydrate timings from the JSON file of a profile. If not present, returns empty timings.
+    /// Files in an older format are migrated, they are written in the current format the next time timings are stored.
+    pub fn read_from_file(year: Option<Year>, profile: Option<&str>) -> Self {
+        let s = fs::read_to_string(Self::file_path(year, profile))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from);
 
@@ -45,32 +145,82 @@ impl Timings {
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Overwritten timings are kept in the history.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
+        let mut history = self.history.clone();
 
         for timing in &new.data {
             data.push(timing.clone());
         }
 
         for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
+            if data.iter().any(|t| t.day == timing.day) {
+                history.push(timing.clone());
+            } else {
                 data.push(timing.clone());
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        data.sort_unstable_by_key(|a| a.day);
+        Timings { data, history }
+    }
+
+    /// Sets the time and commit at which all timings were measured.
+    pub fn stamp(mut self, timestamp: u64, commit: Option<&str>) -> Self {
+        for timing in &mut self.data {
+            timing.timestamp = Some(timestamp);
+            timing.commit = commit.map(Into::into);
+        }
+        self
+    }
+
+    /// Past timings of a day, oldest first, including the latest one.
+    pub fn history_of(&self, day: Day) -> impl Iterator<Item = &Timing> {
+        self.history
+            .iter()
+            .chain(self.data.iter())
+            .filter(move |t| t.day == day)
+    }
+
+    /// Steps of `new` whose execution time exceeds the stored one by more than `threshold` percent.
+    pub fn regressions(&self, new: &Self, threshold: f64) -> Vec<Regression> {
+        let mut regressions = vec![];
+
+        for timing in &new.data {
+            let Some(previous) = self.data.iter().find(|t| t.day == timing.day) else {
+                continue;
+            };
+
+            for step in [Step::Parse, Step::Part(1), Step::Part(2)] {
+                let (Some(before), Some(after)) = (previous.step(step), timing.step(step)) else {
+                    continue;
+                };
+
+                let regression = Regression {
+                    day: timing.day,
+                    step,
+                    previous: before.duration,
+                    current: after.duration,
+                };
+
+                if !before.duration.is_zero() && regression.percent() > threshold {
+                    regressions.push(regression);
+                }
+            }
+        }
+
+        regressions
     }
 
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
-        self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
+        self.data.iter().map(Timing::total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    /// Whether the latest timing of a day benched all of its parts.
     pub fn is_day_complete(&self, day: Day) -> bool {
-        self.data
-            .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+        self.data.iter().any(|t| t.day == day && t.is_complete())
     }
 }
 
@@ -80,11 +230,21 @@ impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(SCHEMA_VERSION)),
+        );
+
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
         );
 
+        map.insert(
+            "history".into(),
+            JsonValue::Array(value.history.iter().map(JsonValue::from).collect()),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -95,19 +255,81 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let parse_timing = match json.get("version") {
+            None => migrate_v1_timing,
+            Some(JsonValue::Number(x)) if *x == f64::from(SCHEMA_VERSION) => {
+                |value: &JsonValue| Timing::try_from(value)
+            }
+            Some(_) => {
+                return Err(format!(
+                    "expected `json.version` to be {SCHEMA_VERSION}, or no version for timings of version 1."
+                ))
+            }
+        };
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
+        // NOTE: history is optional to stay compatible with timings stored before it was tracked.
+        let json_history = match json.get("history") {
+            Some(history) => history
+                .get::<Vec<JsonValue>>()
+                .ok_or("expected `json.history` to be an array.")?
+                .as_slice(),
+            None => &[],
+        };
+
         Ok(Timings {
             data: json_data
                 .iter()
-                .map(Timing::try_from)
+                .map(parse_timing)
                 .collect::<Result<_, _>>()?,
+            history: json_history
+                .iter()
+                .map(parse_timing)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&StepTiming> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &StepTiming) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "nanos".into(),
+            JsonValue::Number(value.duration.as_nanos() as f64),
+        );
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("stats".into(), stats_to_json(value.stats));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for StepTiming {
+    type Error = ();
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value.get::<HashMap<String, JsonValue>>().ok_or(())?;
+
+        let number = |key: &str| json.get(key).and_then(|v| v.get::<f64>()).ok_or(());
+
+        Ok(StepTiming {
+            duration: Duration::from_nanos(*number("nanos")? as u64),
+            samples: *number("samples")? as u128,
+            stats: json.get("stats").map_or(Ok(None), stats_from_json)?,
         })
     }
 }
@@ -119,25 +341,31 @@ impl From<&Timing> for JsonValue {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
-        map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        for (key, step) in [
+            ("parse", &value.parse),
+            ("part_1", &value.part_1),
+            ("part_2", &value.part_2),
+        ] {
+            map.insert(
+                key.into(),
+                step.as_ref().map_or(JsonValue::Null, Into::into),
+            );
+        }
 
+        #[allow(clippy::cast_precision_loss)]
         map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "timestamp".into(),
+            value
+                .timestamp
+                .map_or(JsonValue::Null, |x| JsonValue::Number(x as f64)),
         );
-
         map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
         );
 
         JsonValue::Object(map)
@@ -152,82 +380,206 @@ impl TryFrom<&JsonValue> for Timing {
             .get::<HashMap<String, JsonValue>>()
             .ok_or("Expected timing to be a JSON object.")?;
 
-        let day = json
-            .get("day")
-            .and_then(|v| v.get::<String>())
-            .and_then(|day| Day::from_str(day).ok())
-            .ok_or("Expected timing.day to be a Day struct.")?;
+        let step = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(v) => StepTiming::try_from(v)
+                .map(Some)
+                .map_err(|()| format!("Expected timing.{key} to be null or a step timing.")),
+        };
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+        Ok(Timing {
+            day: parse_day(json)?,
+            parse: step("parse")?,
+            part_1: step("part_1")?,
+            part_2: step("part_2")?,
+            timestamp: parse_timestamp(json)?,
+            commit: parse_commit(json)?,
+        })
+    }
+}
 
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+/// Reads a timing of the version 1 format, where steps are formatted durations with optional stats next to them.
+fn migrate_v1_timing(value: &JsonValue) -> Result<Timing, String> {
+    let json = value
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("Expected timing to be a JSON object.")?;
+
+    let step = |key: &str, required: bool| {
+        let duration = match json.get(key) {
+            None if !required => return Ok(None),
+            Some(JsonValue::Null) => return Ok(None),
+            Some(JsonValue::String(s)) => parse_duration(s),
+            _ => None,
+        }
+        .ok_or(format!("Expected timing.{key} to be null or a duration."))?;
+
+        let stats = json
+            .get(&format!("{key}_stats"))
+            .map_or(Ok(None), stats_from_json)
+            .map_err(|()| format!("Expected timing.{key}_stats to be null or a stats object."))?;
+
+        Ok::<_, String>(Some(StepTiming {
+            duration,
+            samples: 0,
+            stats,
+        }))
+    };
+
+    Ok(Timing {
+        day: parse_day(json)?,
+        parse: step("parse", false)?,
+        part_1: step("part_1", true)?,
+        part_2: step("part_2", true)?,
+        timestamp: parse_timestamp(json)?,
+        commit: parse_commit(json)?,
+    })
+}
 
-        let total_nanos = json
-            .get("total_nanos")
-            .and_then(|v| v.get::<f64>().copied())
-            .ok_or("Expected timing.total_nanos to be a number.")?;
+/// Parses a duration formatted with `{:.1?}`, e.g. `74.1ms`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn parse_duration(s: &str) -> Option<Duration> {
+    let (value, factor) = [("ns", 1_f64), ("µs", 1e3), ("ms", 1e6), ("s", 1e9)]
+        .into_iter()
+        .find_map(|(unit, factor)| Some((s.strip_suffix(unit)?, factor)))?;
 
-        Ok(Timing {
-            day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
-            total_nanos,
-        })
+    let value: f64 = value.parse().ok()?;
+    Some(Duration::from_nanos((value * factor).round() as u64))
+}
+
+fn parse_day(json: &HashMap<String, JsonValue>) -> Result<Day, String> {
+    json.get("day")
+        .and_then(|v| v.get::<String>())
+        .and_then(|day| Day::from_str(day).ok())
+        .ok_or("Expected timing.day to be a Day struct.".into())
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn parse_timestamp(json: &HashMap<String, JsonValue>) -> Result<Option<u64>, String> {
+    match json.get("timestamp") {
+        None | Some(JsonValue::Null) => Ok(None),
+        Some(JsonValue::Number(x)) => Ok(Some(*x as u64)),
+        Some(_) => Err("Expected timing.timestamp to be null or a number.".into()),
+    }
+}
+
+fn parse_commit(json: &HashMap<String, JsonValue>) -> Result<Option<String>, String> {
+    match json.get("commit") {
+        None | Some(JsonValue::Null) => Ok(None),
+        Some(JsonValue::String(x)) => Ok(Some(x.clone())),
+        Some(_) => Err("Expected timing.commit to be null or string.".into()),
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
+/// Fixtures shared by the tests of all modules that work with timings.
 #[cfg(feature = "test_lib")]
-mod tests {
-    use crate::day;
+pub(crate) mod test_helpers {
+    use std::time::Duration;
+
+    use crate::{day, template::Day};
+
+    use super::{StepTiming, Timing, Timings};
+
+    /// A step that took `micros` microseconds, benched with 10 samples.
+    pub fn step(micros: u64) -> Option<StepTiming> {
+        Some(StepTiming {
+            duration: Duration::from_micros(micros),
+            samples: 10,
+            stats: None,
+        })
+    }
 
-    use super::{Timing, Timings};
+    /// The timing of a day, without a timestamp or commit.
+    pub fn timing(
+        day: Day,
+        parse: Option<StepTiming>,
+        part_1: Option<StepTiming>,
+        part_2: Option<StepTiming>,
+    ) -> Timing {
+        Timing {
+            day,
+            parse,
+            part_1,
+            part_2,
+            timestamp: None,
+            commit: None,
+        }
+    }
 
-    fn get_mock_timings() -> Timings {
+    /// Timings of days 1, 2 and 4. Day 2 has a parse step, day 4 is missing part 2.
+    pub fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
-                Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
-                },
-                Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
-                },
-                Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: None,
-                    total_nanos: 4e+10,
-                },
+                timing(day!(1), None, step(10_000), step(20_000)),
+                timing(day!(2), step(5_000), step(30_000), step(40_000)),
+                timing(day!(4), None, step(40_000), None),
             ],
+            history: vec![],
         }
     }
+}
 
+#[cfg(feature = "test_lib")]
+mod tests {
     mod deserialization {
+        use std::time::Duration;
+
         use crate::{day, template::timings::Timings};
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "parse": null, "part_1": { "nanos": 1000000, "samples": 10, "stats": null }, "part_2": null }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            let part_1 = timing.part_1.unwrap();
+            assert_eq!(part_1.duration, Duration::from_millis(1));
+            assert_eq!(part_1.samples, 10);
             assert_eq!(timing.part_2, None);
-            assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.total_nanos(), 1_000_000_f64);
+        }
+
+        #[test]
+        fn migrates_v1_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "74.1ms", "part_2": null, "total_nanos": 74100000 }, { "day": "02", "parse": "12.0µs", "part_1": "1.5s", "part_2": "900.0ns", "total_nanos": 0, "part_1_stats": { "mean": 1500000000, "min": 1, "median": 2, "p95": 3, "max": 4, "std_dev": 5 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data.len(), 2);
+            assert_eq!(
+                timings.data[0].part_1.unwrap().duration,
+                Duration::from_micros(74_100)
+            );
+            assert_eq!(timings.data[0].part_1.unwrap().samples, 0);
+            assert_eq!(timings.data[0].part_2, None);
+            assert_eq!(timings.data[0].parse, None);
+            assert_eq!(
+                timings.data[1].parse.unwrap().duration,
+                Duration::from_micros(12)
+            );
+            assert_eq!(
+                timings.data[1].part_1.unwrap().duration,
+                Duration::from_millis(1500)
+            );
+            assert_eq!(
+                timings.data[1].part_1.unwrap().stats.unwrap().mean,
+                Duration::from_millis(1500)
+            );
+            assert_eq!(
+                timings.data[1].part_2.unwrap().duration,
+                Duration::from_nanos(900)
+            );
+        }
+
+        #[test]
+        fn handles_json_history() {
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": { "nanos": 1000000, "samples": 10, "stats": null }, "part_2": null, "timestamp": 1700000000, "commit": "abc1234" }], "history": [{ "day": "01", "part_1": { "nanos": 2000000, "samples": 10, "stats": null }, "part_2": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].timestamp, Some(1_700_000_000));
+            assert_eq!(timings.data[0].commit, Some("abc1234".to_string()));
+            assert_eq!(timings.history.len(), 1);
+            assert_eq!(timings.history[0].timestamp, None);
+            assert_eq!(timings.history_of(day!(1)).count(), 2);
         }
 
         #[test]
@@ -250,10 +602,27 @@ mod tests {
             let json = r#"{ "data": [{ "day": "01" }, { "day": "26" }, { "day": "02", "part_2": null, "total_nanos": 0 }] }"#.to_string();
             Timings::try_from(json).unwrap();
         }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_unknown_versions() {
+            let json = r#"{ "version": 3, "data": [] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
+
+        #[test]
+        fn rejects_versions_other_than_the_current_one() {
+            let json = r#"{ "version": 1, "data": [] }"#.to_string();
+            assert_eq!(
+                Timings::try_from(json).unwrap_err(),
+                "expected `json.version` to be 2, or no version for timings of version 1."
+            );
+        }
     }
 
     mod serialization {
-        use super::get_mock_timings;
+        use crate::template::timings::test_helpers::get_mock_timings;
+        use crate::template::timings::Timings;
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
@@ -273,6 +642,16 @@ mod tests {
                 3
             );
         }
+
+        #[test]
+        fn round_trips_timings() {
+            let timings = get_mock_timings();
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data.len(), 3);
+            assert_eq!(parsed.data[1].part_1, timings.data[1].part_1);
+            assert_eq!(parsed.data[2].part_2, None);
+        }
     }
 
     mod is_day_complete {
@@ -281,18 +660,23 @@ mod tests {
             template::timings::{Timing, Timings},
         };
 
+        use crate::template::timings::test_helpers::step;
+
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
-                    total_nanos: 3_000_000_000_f64,
+                    parse: None,
+                    part_1: step(1),
+                    part_2: step(2),
+                    timestamp: None,
+                    commit: None,
                 }],
+                history: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -300,13 +684,16 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    parse: None,
+                    part_1: step(1),
                     part_2: None,
-                    total_nanos: 1_000_000_000_f64,
+                    timestamp: None,
+                    commit: None,
                 }],
+                history: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -314,13 +701,33 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
-                    total_nanos: 0.0,
+                    timestamp: None,
+                    commit: None,
+                }],
+                history: vec![],
+            };
+
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+
+        #[test]
+        fn handles_single_part_days() {
+            let timings = Timings {
+                data: vec![Timing {
+                    day: day!(25),
+                    parse: None,
+                    part_1: step(1),
+                    part_2: None,
+                    timestamp: None,
+                    commit: None,
                 }],
+                history: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(25)), true);
         }
     }
 
@@ -330,7 +737,7 @@ mod tests {
             template::timings::{Timing, Timings},
         };
 
-        use super::get_mock_timings;
+        use crate::template::timings::test_helpers::get_mock_timings;
 
         #[test]
         fn handles_disjunct_timings() {
@@ -338,10 +745,13 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
-                    total_nanos: 0_f64,
+                    timestamp: None,
+                    commit: None,
                 }],
+                history: vec![],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -358,18 +768,24 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
-                    total_nanos: 0_f64,
+                    timestamp: None,
+                    commit: None,
                 }],
+                history: vec![],
             };
             let merged = timings.merge(&other);
 
             assert_eq!(merged.data.len(), 3);
             assert_eq!(merged.data[0].day, day!(1));
             assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[1].total_nanos, 0_f64);
+            assert_eq!(merged.data[1].total_nanos(), 0_f64);
             assert_eq!(merged.data[2].day, day!(4));
+            assert_eq!(merged.history.len(), 1);
+            assert_eq!(merged.history[0].total_nanos(), 7.5e+7);
+            assert_eq!(merged.history_of(day!(2)).count(), 2);
         }
 
         #[test]
@@ -388,4 +804,51 @@ mod tests {
             assert_eq!(merged.data.len(), 3);
         }
     }
+
+    mod regressions {
+        use std::time::Duration;
+
+        use crate::{
+            day,
+            template::{
+                runner::Step,
+                timings::{
+                    test_helpers::{step, timing},
+                    Timings,
+                },
+            },
+        };
+
+        fn timings(part_1: u64, part_2: Option<u64>) -> Timings {
+            Timings {
+                data: vec![timing(day!(1), None, step(part_1), part_2.and_then(step))],
+                history: vec![],
+            }
+        }
+
+        #[test]
+        fn detects_slower_steps() {
+            let regressions = timings(100, Some(100)).regressions(&timings(120, Some(100)), 10_f64);
+            assert_eq!(regressions.len(), 1);
+            assert_eq!(regressions[0].step, Step::Part(1));
+            assert_eq!(regressions[0].previous, Duration::from_micros(100));
+            assert_eq!(regressions[0].current, Duration::from_micros(120));
+            assert!((regressions[0].percent() - 20_f64).abs() < 1e-6);
+        }
+
+        #[test]
+        fn ignores_changes_within_threshold() {
+            let regressions = timings(100, Some(100)).regressions(&timings(105, Some(50)), 10_f64);
+            assert!(regressions.is_empty());
+        }
+
+        #[test]
+        fn ignores_steps_without_stored_timing() {
+            let regressions = timings(100, None).regressions(&timings(100, Some(1000)), 10_f64);
+            assert!(regressions.is_empty());
+            assert!(Timings::default()
+                .regressions(&timings(100, Some(100)), 10_f64)
+                .is_empty());
+        }
+    }
 }
@@ -0,0 +1,184 @@
+/// Module that compares two sets of timings, e.g. stored on different machines.
+use std::time::Duration;
+
+use crate::template::{
+    runner::Step,
+    timings::{Timing, Timings},
+    Day, ANSI_BOLD, ANSI_RESET,
+};
+
+/// The change of a step between two sets of timings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StepDiff {
+    pub day: Day,
+    pub step: Step,
+    pub before: Option<Duration>,
+    pub after: Option<Duration>,
+}
+
+impl StepDiff {
+    /// Formatted absolute and relative change, `-` if the step is missing in either set.
+    pub fn format_delta(&self) -> String {
+        match (self.before, self.after) {
+            #[allow(clippy::cast_precision_loss)]
+            (Some(before), Some(after)) => {
+                format_delta(before.as_nanos() as f64, after.as_nanos() as f64)
+            }
+            _ => "-".into(),
+        }
+    }
+}
+
+/// Formats the change from `before` to `after` nanos, e.g. `+1.2ms (+5.0%)`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn format_delta(before: f64, after: f64) -> String {
+    let sign = if after < before { '-' } else { '+' };
+    let delta = Duration::from_nanos((after - before).abs() as u64);
+
+    if before == 0_f64 {
+        return format!("{sign}{delta:.1?}");
+    }
+
+    let percent = (after / before - 1_f64) * 100_f64;
+    format!("{sign}{delta:.1?} ({percent:+.1}%)")
+}
+
+/// Steps present in either set of timings, ordered by day.
+pub fn diff(a: &Timings, b: &Timings) -> Vec<StepDiff> {
+    let mut days: Vec<Day> = a.data.iter().chain(&b.data).map(|t| t.day).collect();
+    days.sort_unstable();
+    days.dedup();
+
+    let find = |timings: &Timings, day: Day| -> Option<Timing> {
+        timings.data.iter().find(|t| t.day == day).cloned()
+    };
+
+    let mut diffs = vec![];
+
+    for day in days {
+        let (before, after) = (find(a, day), find(b, day));
+
+        for step in [Step::Parse, Step::Part(1), Step::Part(2)] {
+            let diff = StepDiff {
+                day,
+                step,
+                before: before
+                    .as_ref()
+                    .and_then(|t| t.step(step))
+                    .map(|x| x.duration),
+                after: after
+                    .as_ref()
+                    .and_then(|t| t.step(step))
+                    .map(|x| x.duration),
+            };
+
+            if diff.before.is_some() || diff.after.is_some() {
+                diffs.push(diff);
+            }
+        }
+    }
+
+    diffs
+}
+
+/// Formatted change of the total execution time of a day, `-` if the day is missing in either set.
+pub fn format_total_delta(a: &Timings, b: &Timings, day: Day) -> String {
+    let total = |timings: &Timings| {
+        timings
+            .data
+            .iter()
+            .find(|t| t.day == day)
+            .map(Timing::total_nanos)
+    };
+
+    match (total(a), total(b)) {
+        (Some(before), Some(after)) => format_delta(before, after),
+        _ => "-".into(),
+    }
+}
+
+/// Prints one line per step with the timing of both sets and their difference.
+pub fn print_diff(diffs: &[StepDiff], a: &str, b: &str) {
+    if diffs.is_empty() {
+        println!("Neither `{a}` nor `{b}` contain any timings.");
+        return;
+    }
+
+    let format = |x: Option<Duration>| x.map_or_else(|| "-".into(), |x| format!("{x:.1?}"));
+
+    println!("{ANSI_BOLD}Day | Step | {a} | {b} | Delta{ANSI_RESET}");
+
+    for diff in diffs {
+        println!(
+            "{} | {} | {} | {} | {}",
+            diff.day,
+            diff.step,
+            format(diff.before),
+            format(diff.after),
+            diff.format_delta()
+        );
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{diff, format_delta, format_total_delta};
+    use crate::{
+        day,
+        template::{
+            runner::Step,
+            timings::{
+                test_helpers::{step, timing},
+                Timing, Timings,
+            },
+        },
+    };
+
+    fn timings(data: Vec<Timing>) -> Timings {
+        Timings {
+            data,
+            history: vec![],
+        }
+    }
+
+    #[test]
+    fn formats_deltas() {
+        assert_eq!(
+            format_delta(1_000_000_f64, 1_200_000_f64),
+            "+200.0µs (+20.0%)"
+        );
+        assert_eq!(format_delta(2_000_f64, 1_000_f64), "-1.0µs (-50.0%)");
+        assert_eq!(format_delta(0_f64, 1_000_f64), "+1.0µs");
+    }
+
+    #[test]
+    fn diffs_steps() {
+        let a = timings(vec![
+            timing(day!(1), None, step(100), step(200)),
+            timing(day!(2), None, step(50), None),
+        ]);
+        let b = timings(vec![
+            timing(day!(1), None, step(80), step(200)),
+            timing(day!(3), None, step(10), step(10)),
+        ]);
+        let diffs = diff(&a, &b);
+
+        assert_eq!(diffs.len(), 5);
+        assert_eq!(diffs[0].day, day!(1));
+        assert_eq!(diffs[0].step, Step::Part(1));
+        assert_eq!(diffs[0].format_delta(), "-20.0µs (-20.0%)");
+        assert_eq!(diffs[1].format_delta(), "+0.0ns (+0.0%)");
+        assert_eq!(diffs[2].day, day!(2));
+        assert_eq!(diffs[2].after, None);
+        assert_eq!(diffs[2].format_delta(), "-");
+        assert_eq!(diffs[3].before, None);
+    }
+
+    #[test]
+    fn formats_total_deltas() {
+        let a = timings(vec![timing(day!(1), None, step(100), step(100))]);
+        let b = timings(vec![timing(day!(1), None, step(150), step(150))]);
+        assert_eq!(format_total_delta(&a, &b, day!(1)), "+100.0µs (+50.0%)");
+        assert_eq!(format_total_delta(&a, &b, day!(2)), "-");
+    }
+}
@@ -0,0 +1,237 @@
+/// Module that exports stored timings to formats other tools can consume, e.g. spreadsheets.
+use std::{cmp::Ordering, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::{
+    day,
+    template::{
+        timings::{StepTiming, Timing, Timings},
+        Day,
+    },
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    /// One row per day with the execution time of each step in nanoseconds.
+    #[default]
+    Csv,
+    /// A table like the readme benchmarks.
+    Markdown,
+    /// The format of `data/timings.json`, without history.
+    Json,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "md" => Ok(Self::Markdown),
+            "json" => Ok(Self::Json),
+            x => Err(format!(
+                "unknown export format `{x}`, expecting `csv`, `md` or `json`."
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SortBy {
+    #[default]
+    Day,
+    /// Slowest day first.
+    Total,
+}
+
+impl FromStr for SortBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(Self::Day),
+            "total" => Ok(Self::Total),
+            x => Err(format!(
+                "unknown sort order `{x}`, expecting `day` or `total`."
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ExportOptions {
+    pub format: ExportFormat,
+    pub sort: SortBy,
+    /// First day to export, inclusive.
+    pub from: Option<Day>,
+    /// Last day to export, inclusive.
+    pub to: Option<Day>,
+}
+
+/// Formats the latest timing of every day selected by `options`.
+pub fn export(timings: &Timings, options: &ExportOptions) -> String {
+    let days = options.from.unwrap_or(day!(1))..=options.to.unwrap_or(day!(25));
+    let mut data: Vec<&Timing> = timings
+        .data
+        .iter()
+        .filter(|t| days.contains(&t.day))
+        .collect();
+
+    match options.sort {
+        SortBy::Day => data.sort_unstable_by_key(|t| t.day),
+        SortBy::Total => data.sort_by(|a, b| {
+            b.total_nanos()
+                .partial_cmp(&a.total_nanos())
+                .unwrap_or(Ordering::Equal)
+        }),
+    }
+
+    match options.format {
+        ExportFormat::Csv => to_csv(&data),
+        ExportFormat::Markdown => to_markdown(&data),
+        ExportFormat::Json => to_json(&data),
+    }
+}
+
+fn steps(timing: &Timing) -> [Option<&StepTiming>; 3] {
+    [
+        timing.parse.as_ref(),
+        timing.part_1.as_ref(),
+        timing.part_2.as_ref(),
+    ]
+}
+
+fn to_csv(data: &[&Timing]) -> String {
+    let mut lines = vec!["day,parse_nanos,part_1_nanos,part_2_nanos,total_nanos".to_string()];
+
+    for timing in data {
+        let mut cells = vec![timing.day.to_string()];
+        cells.extend(
+            steps(timing)
+                .iter()
+                .map(|x| x.map_or_else(String::new, |x| x.duration.as_nanos().to_string())),
+        );
+        cells.push(timing.total_nanos().to_string());
+        lines.push(cells.join(","));
+    }
+
+    lines.join("\n")
+}
+
+fn to_markdown(data: &[&Timing]) -> String {
+    let mut lines = vec![
+        "| Day | Parse | Part 1 | Part 2 | Total |".to_string(),
+        "| :---: | :---: | :---: | :---: | :---: |".to_string(),
+    ];
+
+    for timing in data {
+        let cells: Vec<String> = steps(timing)
+            .iter()
+            .map(|x| x.map_or_else(|| "-".into(), |x| format!("{:.1?}", x.duration)))
+            .collect();
+
+        lines.push(format!(
+            "| {} | `{}` | `{}` | `{}` | `{:.2}ms` |",
+            timing.day.into_inner(),
+            cells[0],
+            cells[1],
+            cells[2],
+            timing.total_nanos() / 1_000_000_f64
+        ));
+    }
+
+    lines.join("\n")
+}
+
+fn to_json(data: &[&Timing]) -> String {
+    let timings = Timings {
+        data: data.iter().map(|&t| t.clone()).collect(),
+        history: vec![],
+    };
+
+    JsonValue::from(timings)
+        .format()
+        .unwrap_or_else(|_| "{}".into())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{export, ExportFormat, ExportOptions, SortBy};
+    use crate::{
+        day,
+        template::timings::{
+            test_helpers::{get_mock_timings, step},
+            Timings,
+        },
+    };
+
+    #[test]
+    fn exports_csv() {
+        let s = export(&get_mock_timings(), &ExportOptions::default());
+        assert_eq!(
+            s,
+            [
+                "day,parse_nanos,part_1_nanos,part_2_nanos,total_nanos",
+                "01,,10000000,20000000,30000000",
+                "02,5000000,30000000,40000000,75000000",
+                "04,,40000000,,40000000",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn exports_markdown() {
+        let options = ExportOptions {
+            format: ExportFormat::Markdown,
+            ..ExportOptions::default()
+        };
+        let s = export(&get_mock_timings(), &options);
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[2], "| 1 | `-` | `10.0ms` | `20.0ms` | `30.00ms` |");
+    }
+
+    #[test]
+    fn exports_json() {
+        let options = ExportOptions {
+            format: ExportFormat::Json,
+            ..ExportOptions::default()
+        };
+        let s = export(&get_mock_timings(), &options);
+        let timings = Timings::try_from(s).unwrap();
+        assert_eq!(timings.data.len(), 3);
+        assert_eq!(timings.data[1].part_1, step(30_000));
+    }
+
+    #[test]
+    fn sorts_by_total() {
+        let options = ExportOptions {
+            sort: SortBy::Total,
+            ..ExportOptions::default()
+        };
+        let s = export(&get_mock_timings(), &options);
+        let days: Vec<&str> = s.lines().skip(1).map(|l| &l[..2]).collect();
+        assert_eq!(days, ["02", "04", "01"]);
+    }
+
+    #[test]
+    fn filters_day_range() {
+        let options = ExportOptions {
+            from: Some(day!(2)),
+            to: Some(day!(3)),
+            ..ExportOptions::default()
+        };
+        let s = export(&get_mock_timings(), &options);
+        assert_eq!(s.lines().count(), 2);
+        assert!(s.ends_with("02,5000000,30000000,40000000,75000000"));
+    }
+
+    #[test]
+    fn parses_options() {
+        assert_eq!("md".parse::<ExportFormat>(), Ok(ExportFormat::Markdown));
+        assert_eq!("total".parse::<SortBy>(), Ok(SortBy::Total));
+        assert!("xml".parse::<ExportFormat>().is_err());
+    }
+}
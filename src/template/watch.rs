@@ -0,0 +1,133 @@
+/// Module that watches files for changes by polling their metadata, without platform-specific notifiers.
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::{data_dir, Day, Year};
+
+use super::{run_multi::get_path_for_bin, runner::InputSource};
+
+/// How often the watched files are polled.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Modification time and size of every file below a set of paths.
+type Snapshot = HashMap<PathBuf, (SystemTime, u64)>;
+
+/// The files that affect the result of a day: its module, the helpers and its data files.
+pub fn paths_for_day(year: Option<Year>, day: Day, input: &InputSource) -> Vec<PathBuf> {
+    let data_dir = data_dir(year);
+    let mut paths = vec![
+        PathBuf::from(get_path_for_bin(year, day)),
+        PathBuf::from("src/helpers"),
+        data_dir.join("examples").join(format!("{day}.txt")),
+        data_dir.join("examples").join(format!("{day}-2.txt")),
+    ];
+
+    match input {
+        InputSource::Day => paths.push(data_dir.join("inputs").join(format!("{day}.txt"))),
+        InputSource::Path(path) => paths.push(path.clone()),
+        InputSource::Stdin => {}
+    }
+
+    paths
+}
+
+/// Collects the metadata of all files below `paths`. Paths that do not exist are skipped,
+/// so that creating them is picked up as a change.
+fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    fn visit(path: &Path, snapshot: &mut Snapshot) {
+        let Ok(metadata) = fs::metadata(path) else {
+            return;
+        };
+
+        if metadata.is_dir() {
+            if let Ok(entries) = fs::read_dir(path) {
+                entries
+                    .flatten()
+                    .for_each(|entry| visit(&entry.path(), snapshot));
+            }
+        } else if let Ok(modified) = metadata.modified() {
+            snapshot.insert(path.to_path_buf(), (modified, metadata.len()));
+        }
+    }
+
+    let mut snapshot = Snapshot::new();
+    paths.iter().for_each(|path| visit(path, &mut snapshot));
+    snapshot
+}
+
+/// The files that were created, changed or removed between two snapshots.
+fn changed_files(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = after
+        .iter()
+        .filter(|(path, metadata)| before.get(*path) != Some(metadata))
+        .chain(before.iter().filter(|(path, _)| !after.contains_key(*path)))
+        .map(|(path, _)| path.clone())
+        .collect();
+
+    changed.sort();
+    changed
+}
+
+/// Calls `on_change` once, then again whenever a file below `paths` changes. Never returns.
+pub fn watch(paths: &[PathBuf], interval: Duration, mut on_change: impl FnMut(&[PathBuf])) -> ! {
+    let mut last = snapshot(paths);
+    on_change(&[]);
+
+    loop {
+        thread::sleep(interval);
+
+        let current = snapshot(paths);
+        let changed = changed_files(&last, &current);
+
+        if !changed.is_empty() {
+            last = current;
+            on_change(&changed);
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{changed_files, snapshot};
+    use std::fs;
+
+    #[test]
+    fn detects_changed_files() {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        let nested = dir.join("nested");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(dir.join("a.txt"), "a").unwrap();
+
+        let paths = [dir.clone(), dir.join("missing.txt")];
+        let before = snapshot(&paths);
+        assert_eq!(before.len(), 1);
+        assert!(changed_files(&before, &snapshot(&paths)).is_empty());
+
+        fs::write(dir.join("a.txt"), "changed").unwrap();
+        fs::write(nested.join("b.txt"), "b").unwrap();
+        fs::write(dir.join("missing.txt"), "c").unwrap();
+        let after = snapshot(&paths);
+
+        assert_eq!(
+            changed_files(&before, &after),
+            vec![
+                dir.join("a.txt"),
+                dir.join("missing.txt"),
+                nested.join("b.txt")
+            ]
+        );
+
+        fs::remove_file(dir.join("a.txt")).unwrap();
+        assert_eq!(
+            changed_files(&after, &snapshot(&paths)),
+            vec![dir.join("a.txt")]
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}